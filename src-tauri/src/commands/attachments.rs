@@ -0,0 +1,80 @@
+use crate::commands::error::CommandError;
+use crate::commands::jots::current_vault;
+use crate::config::AppConfig;
+use crate::db::{Database, PoolError};
+use crate::jots::frontmatter::FrontmatterSchema;
+use crate::jots::models::{Attachment, Jot, JotError};
+use crate::jots::{attachments, unit_of_work};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Attach a file (e.g. a pasted image) to a jot
+/// Stored in the vault's attachment folder (Obsidian's setting) and embedded as ![[file]]
+#[tauri::command]
+pub async fn add_jot_attachment(
+    id: String,
+    file_name: String,
+    data: Vec<u8>,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<Jot, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let options = config.write_options();
+
+    db.write(move |conn| {
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+            uow.add_attachment(&id, &file_name, &data)
+        })
+    })
+    .await
+    .map_err(CommandError::from)
+}
+
+/// List the attachments a jot embeds
+#[tauri::command]
+pub async fn list_jot_attachments(
+    id: String,
+    db: State<'_, Database>,
+) -> Result<Vec<Attachment>, CommandError> {
+    db.read(move |conn| attachments::get_jot_attachments(conn, &id))
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Garbage-collect attachments (see attachments::collect_garbage)
+/// The vault's notes are scanned, only if anything is orphaned, on the blocking thread pool
+/// before the writer lock is taken
+pub(crate) async fn collect_garbage(
+    db: &Database,
+    vault_path: PathBuf,
+    schema: FrontmatterSchema,
+) -> Result<Vec<String>, JotError> {
+    if !db.read(attachments::has_orphans).await? {
+        return Ok(vec![]);
+    }
+
+    let scan_path = vault_path.clone();
+    let notes = tokio::task::spawn_blocking(move || attachments::embedded_in_notes(&scan_path))
+        .await
+        .map_err(|e| JotError::from(PoolError::TaskFailed(e.to_string())))??;
+    db.write(move |conn| attachments::collect_garbage(conn, &vault_path, &schema, &notes))
+        .await
+}
+
+/// Delete attachments no jot, trashed jot or vault note embeds any more
+/// Returns the file names removed
+#[tauri::command]
+pub async fn collect_attachment_garbage(
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<Vec<String>, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let schema = config.frontmatter.clone();
+
+    collect_garbage(&db, vault_pathbuf, schema)
+        .await
+        .map_err(CommandError::from)
+}
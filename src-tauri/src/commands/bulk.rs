@@ -0,0 +1,68 @@
+use crate::commands::error::CommandError;
+use crate::commands::jots::current_vault;
+use crate::config::AppConfig;
+use crate::db::Database;
+use crate::jots::models::{BulkOperation, BulkTarget, JotError};
+use crate::jots::{bulk, unit_of_work};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Outcome of a bulk operation for one jot
+#[derive(Debug, Serialize)]
+pub struct BulkItemResult {
+    pub id: String,
+    pub ok: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<CommandError>,
+}
+
+/// Per-jot outcomes of a bulk operation
+#[derive(Debug, Serialize)]
+pub struct BulkReport {
+    pub results: Vec<BulkItemResult>,
+    pub succeeded: u32,
+    pub failed: u32,
+}
+
+/// Apply one operation to a list of jots or every jot matching a search query
+/// Runs in a single transaction; a failing jot is rolled back on its own and reported
+#[tauri::command]
+pub async fn bulk_jots(
+    target: BulkTarget,
+    operation: BulkOperation,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<BulkReport, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let operation = bulk::validate(&operation)?;
+    let options = config.write_options();
+
+    let outcomes = db
+        .write(move |conn| {
+            let ids = bulk::resolve_target(conn, &target)?;
+            unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+                Ok(bulk::run(uow, &ids, &operation))
+            })
+        })
+        .await
+        .map_err(|e: JotError| -> CommandError { e.into() })?;
+
+    let results: Vec<BulkItemResult> = outcomes
+        .into_iter()
+        .map(|(id, result)| BulkItemResult {
+            id,
+            ok: result.is_ok(),
+            error: result.err().map(CommandError::from),
+        })
+        .collect();
+    let succeeded = results.iter().filter(|r| r.ok).count() as u32;
+
+    Ok(BulkReport {
+        failed: results.len() as u32 - succeeded,
+        succeeded,
+        results,
+    })
+}
@@ -0,0 +1,178 @@
+use crate::db::PoolError;
+use crate::jots::models::{JotError, ValidationRule};
+use serde::Serialize;
+
+/// Stable error codes the frontend can match on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    NotFound,
+    AlreadyExists,
+    Conflict,
+    FileError,
+    ParseError,
+    DbError,
+    DbUnavailable,
+    VaultNotConfigured,
+    ValidationError,
+    NetworkError,
+    Internal,
+}
+
+/// Structured context for an error, serialized flat next to `code`/`message`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum ErrorDetails {
+    /// The jot (or revision) the operation was about
+    Jot { id: String },
+    /// The input field that failed validation
+    Field { field: String },
+    /// The input field that broke a content rule, and the rule
+    Rule { field: String, rule: ValidationRule },
+    /// The file that couldn't be parsed
+    File { file: String },
+}
+
+/// Error returned by every Tauri command
+/// Serializes as `{ code, message, details? }`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommandError {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<ErrorDetails>,
+}
+
+impl CommandError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: ErrorDetails) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<JotError> for CommandError {
+    fn from(err: JotError) -> Self {
+        let message = err.to_string();
+        match err {
+            JotError::NotFound(id) => {
+                Self::new(ErrorCode::NotFound, message).with_details(ErrorDetails::Jot { id })
+            }
+            JotError::AlreadyExists(id) => {
+                Self::new(ErrorCode::AlreadyExists, message).with_details(ErrorDetails::Jot { id })
+            }
+            JotError::Conflict(id) => {
+                Self::new(ErrorCode::Conflict, message).with_details(ErrorDetails::Jot { id })
+            }
+            JotError::FileError(_) => Self::new(ErrorCode::FileError, message),
+            JotError::ParseError { file, .. } => {
+                Self::new(ErrorCode::ParseError, message).with_details(ErrorDetails::File { file })
+            }
+            JotError::YamlError(_) => Self::new(ErrorCode::ParseError, message),
+            JotError::DbError(_) => Self::new(ErrorCode::DbError, message),
+            JotError::DbUnavailable(_) => Self::new(ErrorCode::DbUnavailable, message),
+            JotError::VaultNotConfigured => Self::new(ErrorCode::VaultNotConfigured, message),
+            JotError::NetworkError(_) => Self::new(ErrorCode::NetworkError, message),
+            JotError::ValidationError { field, rule, .. } => {
+                let details = match rule {
+                    Some(rule) => ErrorDetails::Rule { field, rule },
+                    None => ErrorDetails::Field { field },
+                };
+                Self::new(ErrorCode::ValidationError, message).with_details(details)
+            }
+        }
+    }
+}
+
+impl From<PoolError> for CommandError {
+    fn from(err: PoolError) -> Self {
+        JotError::from(err).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_not_found_serialization() {
+        let err = CommandError::from(JotError::NotFound("jot-1".to_string()));
+
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            json!({
+                "code": "not_found",
+                "message": "Jot not found: jot-1",
+                "details": { "id": "jot-1" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_validation_error_has_field() {
+        let err = CommandError::from(JotError::validation("content", "Content cannot be empty"));
+
+        assert_eq!(err.code, ErrorCode::ValidationError);
+        assert_eq!(
+            err.details,
+            Some(ErrorDetails::Field {
+                field: "content".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_rule_violation_serialization() {
+        let err = CommandError::from(JotError::rule_violation(
+            "content",
+            ValidationRule::MaxLength,
+            "Content exceeds 280 characters",
+        ));
+
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            json!({
+                "code": "validation_error",
+                "message": "Invalid content: Content exceeds 280 characters",
+                "details": { "field": "content", "rule": "max_length" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_error_has_file() {
+        let err = CommandError::from(JotError::ParseError {
+            file: ".scribel/jots/jot-1.md".to_string(),
+            message: "Missing frontmatter delimiters".to_string(),
+        });
+
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(value["code"], "parse_error");
+        assert_eq!(value["details"]["file"], ".scribel/jots/jot-1.md");
+    }
+
+    #[test]
+    fn test_details_omitted_when_absent() {
+        let err = CommandError::from(JotError::VaultNotConfigured);
+
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(value["code"], "vault_not_configured");
+        assert!(value.get("details").is_none());
+    }
+}
@@ -0,0 +1,29 @@
+use crate::commands::error::CommandError;
+use crate::commands::jots::current_vault;
+use crate::db::Database;
+use crate::jots::models::{ExportFormat, ExportSummary, JotError, JotFilter};
+use crate::jots::{export, index};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Export the jots matching a listing filter (default: all unarchived jots)
+/// Writes one file at `destination`: Markdown, JSON, CSV, HTML or a zip of the raw files
+#[tauri::command]
+pub async fn export_jots(
+    filter: Option<JotFilter>,
+    format: ExportFormat,
+    destination: String,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+) -> Result<ExportSummary, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let filter = filter.unwrap_or_default();
+
+    db.read(move |conn| {
+        let jots = index::get_filtered_jots(conn, &filter)?;
+        export::export_jots(&vault_pathbuf, &jots, format, &PathBuf::from(destination))
+    })
+    .await
+    .map_err(|e: JotError| e.into())
+}
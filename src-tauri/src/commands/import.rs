@@ -0,0 +1,38 @@
+use crate::commands::error::CommandError;
+use crate::commands::jots::current_vault;
+use crate::config::AppConfig;
+use crate::db::Database;
+use crate::jots::models::{ImportReport, ImportSource, JotError};
+use crate::jots::{import, index, unit_of_work};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Import notes exported from another tool (a folder or file, depending on the source)
+/// Original timestamps and tags are kept; notes matching an existing jot are skipped as duplicates
+#[tauri::command]
+pub async fn import_jots(
+    source: ImportSource,
+    path: String,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<ImportReport, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let path = PathBuf::from(path);
+    let options = config.write_options();
+
+    db.write(move |conn| {
+        let known_hashes: HashSet<u64> = index::get_all_jots(conn)?
+            .iter()
+            .map(|jot| import::content_hash(&jot.content))
+            .collect();
+
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+            import::import_jots(uow, source, &path, known_hashes)
+        })
+    })
+    .await
+    .map_err(|e: JotError| e.into())
+}
@@ -1,126 +1,248 @@
-use crate::jots::models::{CreateJotInput, Jot, UpdateJotInput};
-use crate::jots::{index, storage};
-use rusqlite::Connection;
+use crate::commands::attachments::collect_garbage;
+use crate::commands::error::{CommandError, ErrorCode};
+use crate::commands::links::spawn_link_capture;
+use crate::config::AppConfig;
+use crate::db::Database;
+use crate::jots::models::{
+    ConsistencyReport, CreateJotInput, DiffLine, Jot, JotError, JotFilter, JotListQuery, JotPage,
+    JotRevision, MergeOriginals, MergeResult, TrashedJot, UpdateJotInput,
+};
+use crate::jots::{combine, consistency, history, index, merge, storage, trash, unit_of_work};
 use std::path::PathBuf;
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, State};
+
+/// Current vault path, copied out so the lock isn't held across awaits
+pub(crate) fn current_vault(
+    vault_path: &State<'_, Mutex<String>>,
+) -> Result<PathBuf, CommandError> {
+    let vault = vault_path.lock().map_err(|e| {
+        CommandError::new(ErrorCode::Internal, format!("Vault lock poisoned: {}", e))
+    })?;
+
+    if vault.is_empty() {
+        return Err(JotError::VaultNotConfigured.into());
+    }
+
+    Ok(PathBuf::from(vault.as_str()))
+}
 
 /// Create a new jot
 #[tauri::command]
 pub async fn create_jot(
     content: String,
-    db: State<'_, Mutex<Connection>>,
+    app: AppHandle,
+    db: State<'_, Database>,
     vault_path: State<'_, Mutex<String>>,
-) -> Result<Jot, String> {
-    let vault = vault_path
-        .lock()
-        .map_err(|e| format!("Vault lock poisoned: {}", e))?;
-    let vault_pathbuf = PathBuf::from(vault.as_str());
-
-    // Create jot file
-    let jot = storage::create_jot(&vault_pathbuf, &content).map_err(|e| e.to_string())?;
+    config: State<'_, AppConfig>,
+) -> Result<Jot, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let capture_vault = vault_pathbuf.clone();
+    let options = config.write_options();
 
-    // Insert into index
-    let conn = db
-        .lock()
-        .map_err(|e| format!("Database lock poisoned: {}", e))?;
-    index::insert_jot(&conn, &jot).map_err(|e| e.to_string())?;
+    // Create jot file and insert into index
+    let jot = db
+        .write(move |conn| {
+            unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+                uow.create_jot(&content)
+            })
+        })
+        .await
+        .map_err(CommandError::from)?;
 
+    if config.capture_link_metadata {
+        spawn_link_capture(
+            app,
+            db.inner().clone(),
+            capture_vault,
+            config.write_options(),
+            &jot,
+        );
+    }
     Ok(jot)
 }
 
-/// Get paginated list of jots
+/// Get a page of jots
+/// Pass the previous page's `next_cursor` to continue; limit is capped at 500
 #[tauri::command]
 pub async fn get_jots(
-    limit: Option<u32>,
-    offset: Option<u32>,
-    db: State<'_, Mutex<Connection>>,
-) -> Result<Vec<Jot>, String> {
-    let conn = db
-        .lock()
-        .map_err(|e| format!("Database lock poisoned: {}", e))?;
-    let limit = limit.unwrap_or(50).min(500);
-    let offset = offset.unwrap_or(0);
+    query: Option<JotListQuery>,
+    db: State<'_, Database>,
+) -> Result<JotPage, CommandError> {
+    let mut query = query.unwrap_or_default();
+    query.limit = query.limit.clamp(1, 500);
 
-    index::get_jots(&conn, limit, offset).map_err(|e| e.to_string())
+    db.read(move |conn| index::get_jots(conn, &query))
+        .await
+        .map_err(CommandError::from)
 }
 
 /// Get a single jot by ID
 #[tauri::command]
 pub async fn get_jot(
     id: String,
+    db: State<'_, Database>,
     vault_path: State<'_, Mutex<String>>,
-) -> Result<Jot, String> {
-    let vault = vault_path
-        .lock()
-        .map_err(|e| format!("Vault lock poisoned: {}", e))?;
-    let vault_pathbuf = PathBuf::from(vault.as_str());
+    config: State<'_, AppConfig>,
+) -> Result<Jot, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let schema = config.frontmatter.clone();
 
-    storage::read_jot(&vault_pathbuf, &id).map_err(|e| e.to_string())
+    db.read(move |conn| storage::find_jot(conn, &vault_pathbuf, &id, &schema))
+        .await
+        .map_err(CommandError::from)
 }
 
 /// Update jot content
+/// Fails with a conflict if the jot changed since the client loaded it (see UpdateJotInput)
 #[tauri::command]
 pub async fn update_jot(
-    id: String,
-    content: String,
-    db: State<'_, Mutex<Connection>>,
+    input: UpdateJotInput,
+    app: AppHandle,
+    db: State<'_, Database>,
     vault_path: State<'_, Mutex<String>>,
-) -> Result<Jot, String> {
-    let vault = vault_path
-        .lock()
-        .map_err(|e| format!("Vault lock poisoned: {}", e))?;
-    let vault_pathbuf = PathBuf::from(vault.as_str());
-
-    // Update file
-    let jot = storage::update_jot(&vault_pathbuf, &id, &content).map_err(|e| e.to_string())?;
+    config: State<'_, AppConfig>,
+) -> Result<Jot, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let capture_vault = vault_pathbuf.clone();
+    let options = config.write_options();
 
-    // Update index
-    let conn = db
-        .lock()
-        .map_err(|e| format!("Database lock poisoned: {}", e))?;
-    index::update_jot_index(&conn, &jot).map_err(|e| e.to_string())?;
+    // Update file and index, recording the previous revision
+    let jot = db
+        .write(move |conn| {
+            unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+                uow.update_jot(&input)
+            })
+        })
+        .await
+        .map_err(CommandError::from)?;
 
+    if config.capture_link_metadata {
+        spawn_link_capture(
+            app,
+            db.inner().clone(),
+            capture_vault,
+            config.write_options(),
+            &jot,
+        );
+    }
     Ok(jot)
 }
 
-/// Delete a jot
+/// Three-way merge of the client's edit with the jot's current content
+/// Uses `base_content` as the common ancestor; the UI resolves any conflict markers
+/// and retries update_jot with the current version as the new base
+#[tauri::command]
+pub async fn merge_jot_update(
+    input: UpdateJotInput,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<MergeResult, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let schema = config.frontmatter.clone();
+
+    let base = input
+        .base_content
+        .as_deref()
+        .ok_or_else(|| JotError::validation("base_content", "base_content is required"))?;
+    let id = input.id.clone();
+    let current = db
+        .read(move |conn| storage::find_jot(conn, &vault_pathbuf, &id, &schema))
+        .await
+        .map_err(CommandError::from)?;
+
+    Ok(merge::three_way_merge(
+        base,
+        &input.content,
+        &current.content,
+    ))
+}
+
+/// Delete a jot (moves it to the trash)
+/// Attachments it embeds are garbage-collected once it is purged from the trash
 #[tauri::command]
 pub async fn delete_jot(
     id: String,
-    db: State<'_, Mutex<Connection>>,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<(), CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let options = config.write_options();
+
+    // Move file to trash and delete from index
+    db.write(move |conn| {
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| uow.delete_jot(&id))
+    })
+    .await
+    .map_err(CommandError::from)
+}
+
+/// List jots in the trash
+#[tauri::command]
+pub async fn list_trash(
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<Vec<TrashedJot>, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+
+    trash::list_trash(&vault_pathbuf, &config.frontmatter).map_err(CommandError::from)
+}
+
+/// Restore a jot from the trash
+#[tauri::command]
+pub async fn restore_jot(
+    id: String,
+    db: State<'_, Database>,
     vault_path: State<'_, Mutex<String>>,
-) -> Result<(), String> {
-    let vault = vault_path
-        .lock()
-        .map_err(|e| format!("Vault lock poisoned: {}", e))?;
-    let vault_pathbuf = PathBuf::from(vault.as_str());
+    config: State<'_, AppConfig>,
+) -> Result<Jot, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let options = config.write_options();
+
+    // Move file back and re-insert into index
+    db.write(move |conn| {
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| uow.restore_jot(&id))
+    })
+    .await
+    .map_err(CommandError::from)
+}
 
-    // Delete file
-    storage::delete_jot(&vault_pathbuf, &id).map_err(|e| e.to_string())?;
+/// Permanently delete a jot from the trash, then any attachments nothing embeds any more
+#[tauri::command]
+pub async fn purge_trashed_jot(
+    id: String,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<(), CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let schema = config.frontmatter.clone();
 
-    // Delete from index
-    let conn = db
-        .lock()
-        .map_err(|e| format!("Database lock poisoned: {}", e))?;
-    index::delete_jot_index(&conn, &id).map_err(|e| e.to_string())?;
+    trash::purge_jot(&vault_pathbuf, &id).map_err(CommandError::from)?;
+
+    let result = collect_garbage(&db, vault_pathbuf, schema).await;
+    if let Err(e) = result {
+        eprintln!("Failed to collect attachment garbage: {}", e);
+    }
 
     Ok(())
 }
 
-/// Search jots by content
+/// Search jots by content, optionally narrowed by a listing filter (tags, properties, ...)
 #[tauri::command]
 pub async fn search_jots(
     query: String,
+    filter: Option<JotFilter>,
     limit: Option<u32>,
-    db: State<'_, Mutex<Connection>>,
-) -> Result<Vec<Jot>, String> {
-    let conn = db
-        .lock()
-        .map_err(|e| format!("Database lock poisoned: {}", e))?;
+    db: State<'_, Database>,
+) -> Result<Vec<Jot>, CommandError> {
     let limit = limit.unwrap_or(50).min(500);
 
-    index::search_jots(&conn, &query, limit).map_err(|e| e.to_string())
+    db.read(move |conn| index::search_jots(conn, &query, filter.as_ref(), limit))
+        .await
+        .map_err(CommandError::from)
 }
 
 /// Set promoted status for a jot
@@ -128,74 +250,272 @@ pub async fn search_jots(
 pub async fn set_jot_promoted(
     id: String,
     promoted: bool,
-    db: State<'_, Mutex<Connection>>,
+    db: State<'_, Database>,
     vault_path: State<'_, Mutex<String>>,
-) -> Result<Jot, String> {
-    let vault = vault_path
-        .lock()
-        .map_err(|e| format!("Vault lock poisoned: {}", e))?;
-    let vault_pathbuf = PathBuf::from(vault.as_str());
+    config: State<'_, AppConfig>,
+) -> Result<Jot, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let options = config.write_options();
 
-    // Update file
-    let jot = storage::set_promoted(&vault_pathbuf, &id, promoted).map_err(|e| e.to_string())?;
+    // Update file and index, recording the previous revision
+    db.write(move |conn| {
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+            uow.set_promoted(&id, promoted)
+        })
+    })
+    .await
+    .map_err(CommandError::from)
+}
 
-    // Update index
-    let conn = db
-        .lock()
-        .map_err(|e| format!("Database lock poisoned: {}", e))?;
-    index::update_jot_index(&conn, &jot).map_err(|e| e.to_string())?;
+/// List prior revisions of a jot (newest first)
+#[tauri::command]
+pub async fn list_jot_revisions(
+    id: String,
+    db: State<'_, Database>,
+) -> Result<Vec<JotRevision>, CommandError> {
+    db.read(move |conn| history::list_revisions(conn, &id))
+        .await
+        .map_err(CommandError::from)
+}
 
-    Ok(jot)
+/// Diff two revisions of a jot
+/// Without `to_revision`, diffs against the jot's current content
+#[tauri::command]
+pub async fn diff_jot_revisions(
+    from_revision: i64,
+    to_revision: Option<i64>,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<Vec<DiffLine>, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let schema = config.frontmatter.clone();
+
+    db.read(move |conn| {
+        let from = history::get_revision(conn, from_revision)?;
+        let to_content = match to_revision {
+            Some(revision_id) => history::get_revision(conn, revision_id)?.content,
+            None => storage::find_jot(conn, &vault_pathbuf, &from.jot_id, &schema)?.content,
+        };
+
+        Ok(history::diff_lines(&from.content, &to_content))
+    })
+    .await
+    .map_err(|e: JotError| e.into())
+}
+
+/// Restore an older revision's content as a new modification
+#[tauri::command]
+pub async fn restore_jot_revision(
+    revision_id: i64,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<Jot, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let options = config.write_options();
+
+    db.write(move |conn| {
+        let revision = history::get_revision(conn, revision_id)?;
+
+        // Rewrite with the revision's content; the current version is recorded,
+        // so the restore itself can be undone
+        let input = UpdateJotInput {
+            id: revision.jot_id,
+            content: revision.content,
+            base_modified_at: None,
+            base_content: None,
+        };
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| uow.update_jot(&input))
+    })
+    .await
+    .map_err(|e: JotError| e.into())
 }
 
 /// Rebuild jot index from files
 #[tauri::command]
 pub async fn rebuild_jot_index(
-    db: State<'_, Mutex<Connection>>,
+    db: State<'_, Database>,
     vault_path: State<'_, Mutex<String>>,
-) -> Result<u32, String> {
-    let vault = vault_path
-        .lock()
-        .map_err(|e| format!("Vault lock poisoned: {}", e))?;
-    let vault_pathbuf = PathBuf::from(vault.as_str());
-    let jots_dir = vault_pathbuf.join(".scribel/jots");
+    config: State<'_, AppConfig>,
+) -> Result<u32, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let schema = config.frontmatter.clone();
 
-    if !jots_dir.exists() {
-        return Ok(0);
-    }
+    // Rebuild inside one transaction: readers keep seeing the old index until it commits
+    db.write(move |conn| {
+        let tx = conn.transaction()?;
 
-    let conn = db
-        .lock()
-        .map_err(|e| format!("Database lock poisoned: {}", e))?;
-
-    // Clear existing index
-    conn.execute("DELETE FROM jot_index", [])
-        .map_err(|e| e.to_string())?;
-
-    // Scan and index all jot files
-    let mut count = 0;
-    for entry in std::fs::read_dir(&jots_dir).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
-
-        if path.extension().and_then(|s| s.to_str()) == Some("md") {
-            if let Some(file_name) = path.file_stem() {
-                let id = file_name.to_str().unwrap_or("");
-                match storage::read_jot(&vault_pathbuf, id) {
-                    Ok(jot) => {
-                        if let Err(e) = index::insert_jot(&conn, &jot) {
-                            eprintln!("Failed to index {}: {}", id, e);
-                        } else {
-                            count += 1;
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to read {}: {}", id, e);
+        // Clear existing index
+        tx.execute("DELETE FROM jot_index", [])?;
+        tx.execute("DELETE FROM jot_attachments", [])?;
+        tx.execute("DELETE FROM jot_links_external", [])?;
+        tx.execute("DELETE FROM jot_properties", [])?;
+        tx.execute("DELETE FROM jot_tasks", [])?;
+
+        // Scan and index all jot files
+        let mut count = 0;
+        for file_path in storage::list_jot_files(&vault_pathbuf)? {
+            match storage::read_jot_file(&vault_pathbuf, &file_path, &schema) {
+                Ok(jot) => {
+                    if let Err(e) = index::insert_jot(&tx, &jot) {
+                        eprintln!("Failed to index {}: {}", jot.id, e);
+                    } else {
+                        count += 1;
                     }
                 }
+                Err(e) => {
+                    eprintln!("Failed to read {}: {}", file_path, e);
+                }
             }
         }
-    }
 
-    Ok(count)
+        // Reminders are kept (with their snoozed or dismissed state) while their jot exists
+        tx.execute(
+            "DELETE FROM reminders WHERE jot_id NOT IN (SELECT id FROM jot_index)",
+            [],
+        )?;
+
+        tx.commit()?;
+        Ok(count)
+    })
+    .await
+    .map_err(|e: JotError| e.into())
+}
+
+/// Rename every jot file after the configured file name template (after the template changed)
+/// All-or-nothing; returns the number of files whose path changed
+#[tauri::command]
+pub async fn rename_jot_files(
+    db: State<'_, Database>,
+    config: State<'_, AppConfig>,
+    vault_path: State<'_, Mutex<String>>,
+) -> Result<u32, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let options = config.write_options();
+
+    db.write(move |conn| {
+        let jots = index::get_all_jots(conn)?;
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+            let mut renamed = 0;
+            for jot in &jots {
+                if uow.rename_jot(&jot.id)?.file_path != jot.file_path {
+                    renamed += 1;
+                }
+            }
+            Ok(renamed)
+        })
+    })
+    .await
+    .map_err(|e: JotError| e.into())
+}
+
+/// Archive jots (processed, not worth a note)
+/// All-or-nothing; files move to the archive subfolder if enabled in the config
+#[tauri::command]
+pub async fn archive_jots(
+    ids: Vec<String>,
+    db: State<'_, Database>,
+    config: State<'_, AppConfig>,
+    vault_path: State<'_, Mutex<String>>,
+) -> Result<Vec<Jot>, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let move_file = config.archive_to_subfolder;
+    let options = config.write_options();
+
+    db.write(move |conn| {
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+            ids.iter()
+                .map(|id| uow.set_archived(id, true, move_file))
+                .collect()
+        })
+    })
+    .await
+    .map_err(CommandError::from)
+}
+
+/// Unarchive jots, moving files back to .scribel/jots/ if needed (all-or-nothing)
+#[tauri::command]
+pub async fn unarchive_jots(
+    ids: Vec<String>,
+    db: State<'_, Database>,
+    config: State<'_, AppConfig>,
+    vault_path: State<'_, Mutex<String>>,
+) -> Result<Vec<Jot>, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let options = config.write_options();
+
+    db.write(move |conn| {
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+            ids.iter()
+                .map(|id| uow.set_archived(id, false, false))
+                .collect()
+        })
+    })
+    .await
+    .map_err(CommandError::from)
+}
+
+/// Merge jots into one, in chronological order
+/// Originals are trashed unless `originals` is "link" (then the merged jot links to them)
+#[tauri::command]
+pub async fn merge_jots(
+    ids: Vec<String>,
+    originals: Option<MergeOriginals>,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<Jot, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let originals = originals.unwrap_or_default();
+    let options = config.write_options();
+
+    db.write(move |conn| {
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+            combine::merge_jots(uow, &ids, originals)
+        })
+    })
+    .await
+    .map_err(CommandError::from)
+}
+
+/// Split a jot at character offsets (or at blank lines if none are given)
+/// The parts inherit the original's created_at and tags; the original is trashed
+#[tauri::command]
+pub async fn split_jot(
+    id: String,
+    offsets: Option<Vec<usize>>,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<Vec<Jot>, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let offsets = offsets.unwrap_or_default();
+    let options = config.write_options();
+
+    db.write(move |conn| {
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+            combine::split_jot(uow, &id, &offsets)
+        })
+    })
+    .await
+    .map_err(CommandError::from)
+}
+
+/// Check jot files against the index, optionally repairing drift
+#[tauri::command]
+pub async fn check_jot_consistency(
+    repair: Option<bool>,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<ConsistencyReport, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let schema = config.frontmatter.clone();
+
+    let repair = repair.unwrap_or(false);
+
+    db.write(move |conn| consistency::check_consistency(conn, &vault_pathbuf, repair, &schema))
+        .await
+        .map_err(CommandError::from)
 }
@@ -0,0 +1,111 @@
+use crate::commands::error::CommandError;
+use crate::commands::jots::current_vault;
+use crate::config::AppConfig;
+use crate::db::Database;
+use crate::jots::models::{ExternalLink, Jot, JotError};
+use crate::jots::storage::{self, WriteOptions};
+use crate::jots::{unit_of_work, web_clip};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State};
+
+/// Event emitted with the updated jot once link metadata has been captured
+pub const LINKS_CAPTURED_EVENT: &str = "jot-links-captured";
+
+/// Fetch metadata for each URL; unreachable pages are skipped (retried on the next edit)
+fn fetch_all(urls: &[String]) -> Vec<ExternalLink> {
+    urls.iter()
+        .filter_map(|url| match web_clip::fetch_metadata(url) {
+            Ok(link) => Some(link),
+            Err(e) => {
+                eprintln!("Failed to fetch {}: {}", url, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Fetch the jot's pending URLs, then store what was fetched
+/// Pages are fetched outside the database writer so slow sites never block other writes
+async fn capture_links(
+    db: &Database,
+    vault_path: PathBuf,
+    options: WriteOptions,
+    id: String,
+    urls: Vec<String>,
+) -> Result<Option<Jot>, JotError> {
+    let fetched = tokio::task::spawn_blocking(move || fetch_all(&urls))
+        .await
+        .map_err(|e| JotError::NetworkError(e.to_string()))?;
+    if fetched.is_empty() {
+        return Ok(None);
+    }
+
+    db.write(move |conn| {
+        unit_of_work::run_with_options(conn, &vault_path, options, |uow| {
+            uow.set_external_links(&id, fetched)
+        })
+    })
+    .await
+    .map(Some)
+}
+
+/// Capture metadata for the jot's new URLs in the background (offline: nothing is stored)
+pub(crate) fn spawn_link_capture(
+    app: AppHandle,
+    db: Database,
+    vault_path: PathBuf,
+    options: WriteOptions,
+    jot: &Jot,
+) {
+    let urls = web_clip::pending_urls(jot);
+    if urls.is_empty() {
+        return;
+    }
+
+    let id = jot.id.clone();
+    tauri::async_runtime::spawn(async move {
+        match capture_links(&db, vault_path, options, id, urls).await {
+            Ok(Some(jot)) => {
+                if let Err(e) = app.emit(LINKS_CAPTURED_EVENT, &jot) {
+                    eprintln!("Failed to emit {}: {}", LINKS_CAPTURED_EVENT, e);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Link capture failed: {}", e),
+        }
+    });
+}
+
+/// Re-fetch metadata for every URL in a jot (e.g. after going back online)
+/// Fails with network_error if no page could be fetched
+#[tauri::command]
+pub async fn refresh_jot_links(
+    id: String,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<Jot, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let options = config.write_options();
+
+    let lookup_vault = vault_pathbuf.clone();
+    let lookup_id = id.clone();
+    let schema = options.frontmatter.clone();
+    let jot = db
+        .read(move |conn| storage::find_jot(conn, &lookup_vault, &lookup_id, &schema))
+        .await
+        .map_err(CommandError::from)?;
+    let urls = web_clip::extract_urls(&jot.content);
+    if urls.is_empty() {
+        return Ok(jot);
+    }
+
+    match capture_links(&db, vault_pathbuf, options, id, urls).await {
+        Ok(Some(jot)) => Ok(jot),
+        Ok(None) => {
+            Err(JotError::NetworkError("No linked page could be fetched".to_string()).into())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
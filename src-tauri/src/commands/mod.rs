@@ -1 +1,12 @@
+pub mod attachments;
+pub mod bulk;
+pub mod error;
+pub mod export;
+pub mod import;
 pub mod jots;
+pub mod links;
+pub mod reminders;
+pub mod review;
+pub mod stats;
+pub mod tasks;
+pub mod templates;
@@ -0,0 +1,72 @@
+use crate::commands::error::CommandError;
+use crate::db::Database;
+use crate::jots::models::{Reminder, ReminderStatus};
+use crate::jots::reminders;
+use chrono::{Duration, Utc};
+use tauri::{AppHandle, Emitter, State};
+
+/// Event emitted with each reminder when it comes due
+pub const REMINDER_DUE_EVENT: &str = "reminder-due";
+
+/// Snooze period when none is given
+const DEFAULT_SNOOZE_MINUTES: u32 = 10;
+
+/// Emit REMINDER_DUE_EVENT for reminders as they come due, checking every `interval`
+/// Reminders that came due while the app was closed fire on the first check
+pub(crate) fn spawn_reminder_scheduler(
+    app: AppHandle,
+    db: Database,
+    interval: std::time::Duration,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+
+            match db.write(|conn| reminders::take_due(conn, Utc::now())).await {
+                Ok(due) => {
+                    for reminder in due {
+                        if let Err(e) = app.emit(REMINDER_DUE_EVENT, &reminder) {
+                            eprintln!("Failed to emit {}: {}", REMINDER_DUE_EVENT, e);
+                        }
+                    }
+                }
+                Err(e) => eprintln!("Reminder check failed: {}", e),
+            }
+        }
+    });
+}
+
+/// List reminders with the given status (default: pending and fired, soonest first)
+#[tauri::command]
+pub async fn list_reminders(
+    status: Option<ReminderStatus>,
+    db: State<'_, Database>,
+) -> Result<Vec<Reminder>, CommandError> {
+    db.read(move |conn| reminders::list_reminders(conn, status))
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Fire a reminder again in `minutes` (default: 10)
+#[tauri::command]
+pub async fn snooze_reminder(
+    id: i64,
+    minutes: Option<u32>,
+    db: State<'_, Database>,
+) -> Result<Reminder, CommandError> {
+    let minutes = minutes.unwrap_or(DEFAULT_SNOOZE_MINUTES).max(1);
+    let until = Utc::now() + Duration::minutes(i64::from(minutes));
+
+    db.write(move |conn| reminders::snooze(conn, id, until))
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Dismiss a reminder so it no longer fires or shows as active
+#[tauri::command]
+pub async fn dismiss_reminder(id: i64, db: State<'_, Database>) -> Result<Reminder, CommandError> {
+    db.write(move |conn| reminders::dismiss(conn, id))
+        .await
+        .map_err(CommandError::from)
+}
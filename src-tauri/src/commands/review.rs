@@ -0,0 +1,47 @@
+use crate::commands::error::CommandError;
+use crate::commands::jots::current_vault;
+use crate::config::AppConfig;
+use crate::db::Database;
+use crate::jots::models::{JotError, ReviewItem, ReviewState, ReviewStatus};
+use crate::jots::{review, unit_of_work};
+use chrono::Utc;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Get today's review queue (default: 10 jots, highest priority first)
+#[tauri::command]
+pub async fn get_review_queue(
+    limit: Option<u32>,
+    db: State<'_, Database>,
+) -> Result<Vec<ReviewItem>, CommandError> {
+    let limit = limit.unwrap_or(10).min(100);
+
+    db.read(move |conn| review::review_queue(conn, limit, Utc::now()))
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Record the outcome of reviewing a jot
+/// Snoozed jots come back after `snooze_days` (default: 1); promoted/archived ones are promoted/archived
+#[tauri::command]
+pub async fn record_review_outcome(
+    id: String,
+    outcome: ReviewStatus,
+    snooze_days: Option<u32>,
+    db: State<'_, Database>,
+    config: State<'_, AppConfig>,
+    vault_path: State<'_, Mutex<String>>,
+) -> Result<ReviewState, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let snooze_days = snooze_days.unwrap_or(1);
+    let archive_to_subfolder = config.archive_to_subfolder;
+    let options = config.write_options();
+
+    db.write(move |conn| {
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+            uow.record_review(&id, outcome, snooze_days, archive_to_subfolder)
+        })
+    })
+    .await
+    .map_err(|e: JotError| e.into())
+}
@@ -0,0 +1,42 @@
+use crate::commands::error::CommandError;
+use crate::db::Database;
+use crate::jots::models::{JotStats, StatsQuery, TagFrequency, TimelineBucket};
+use crate::jots::stats;
+use chrono::Utc;
+use tauri::State;
+
+/// Jots created per day/week/month, bucketed in the user's local time
+#[tauri::command]
+pub async fn get_jot_timeline(
+    query: StatsQuery,
+    db: State<'_, Database>,
+) -> Result<Vec<TimelineBucket>, CommandError> {
+    db.read(move |conn| stats::timeline(conn, &query))
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Usage over time of the most used tags (default: top 10)
+#[tauri::command]
+pub async fn get_tag_frequency(
+    query: StatsQuery,
+    limit: Option<u32>,
+    db: State<'_, Database>,
+) -> Result<TagFrequency, CommandError> {
+    let limit = limit.unwrap_or(10).min(100) as usize;
+
+    db.read(move |conn| stats::tag_frequency(conn, &query, limit))
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Promotion rate, average time to promotion and streaks
+#[tauri::command]
+pub async fn get_jot_stats(
+    query: StatsQuery,
+    db: State<'_, Database>,
+) -> Result<JotStats, CommandError> {
+    db.read(move |conn| stats::summary(conn, &query, Utc::now()))
+        .await
+        .map_err(CommandError::from)
+}
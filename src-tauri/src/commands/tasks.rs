@@ -0,0 +1,46 @@
+use crate::commands::error::CommandError;
+use crate::commands::jots::current_vault;
+use crate::config::AppConfig;
+use crate::db::Database;
+use crate::jots::models::{Jot, JotError, JotTask, TaskQuery};
+use crate::jots::{tasks, unit_of_work};
+use chrono::Local;
+use std::sync::Mutex;
+use tauri::State;
+
+/// List checkbox tasks across jots (default: open tasks of unarchived jots, soonest due first)
+#[tauri::command]
+pub async fn list_jot_tasks(
+    query: Option<TaskQuery>,
+    db: State<'_, Database>,
+) -> Result<Vec<JotTask>, CommandError> {
+    let query = query.unwrap_or_default();
+    let today = query.today.unwrap_or_else(|| Local::now().date_naive());
+
+    db.read(move |conn| tasks::list_tasks(conn, &query, today))
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Check or uncheck a task, rewriting its line in the jot file
+/// `line` and `text` come from the listed task; fails with a conflict if the line changed
+#[tauri::command]
+pub async fn toggle_jot_task(
+    id: String,
+    line: u32,
+    text: String,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<Jot, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let options = config.write_options();
+
+    db.write(move |conn| {
+        unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+            uow.toggle_task(&id, line, &text)
+        })
+    })
+    .await
+    .map_err(|e: JotError| e.into())
+}
@@ -0,0 +1,89 @@
+use crate::commands::error::CommandError;
+use crate::commands::jots::current_vault;
+use crate::commands::links::spawn_link_capture;
+use crate::config::AppConfig;
+use crate::db::Database;
+use crate::jots::models::{JotError, JotTemplate, TemplateJot, TemplateValues};
+use crate::jots::{templates, unit_of_work};
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+/// List the vault's jot templates
+#[tauri::command]
+pub async fn list_templates(
+    vault_path: State<'_, Mutex<String>>,
+) -> Result<Vec<JotTemplate>, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+
+    templates::list_templates(&vault_pathbuf).map_err(CommandError::from)
+}
+
+/// Create or replace a template
+#[tauri::command]
+pub async fn save_template(
+    name: String,
+    content: String,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+    vault_path: State<'_, Mutex<String>>,
+) -> Result<JotTemplate, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+
+    templates::save_template(
+        &vault_pathbuf,
+        &name,
+        description,
+        &tags.unwrap_or_default(),
+        &content,
+    )
+    .map_err(CommandError::from)
+}
+
+/// Delete a template
+#[tauri::command]
+pub async fn delete_template(
+    name: String,
+    vault_path: State<'_, Mutex<String>>,
+) -> Result<(), CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+
+    templates::delete_template(&vault_pathbuf, &name).map_err(CommandError::from)
+}
+
+/// Create a jot from a template, filling in its placeholders
+/// Returns the jot and where the editor cursor goes
+#[tauri::command]
+pub async fn create_jot_from_template(
+    name: String,
+    values: Option<TemplateValues>,
+    app: AppHandle,
+    db: State<'_, Database>,
+    vault_path: State<'_, Mutex<String>>,
+    config: State<'_, AppConfig>,
+) -> Result<TemplateJot, CommandError> {
+    let vault_pathbuf = current_vault(&vault_path)?;
+    let capture_vault = vault_pathbuf.clone();
+    let template = templates::read_template(&vault_pathbuf, &name)?;
+    let values = values.unwrap_or_default();
+    let options = config.write_options();
+
+    let created = db
+        .write(move |conn| {
+            unit_of_work::run_with_options(conn, &vault_pathbuf, options, |uow| {
+                uow.create_jot_from_template(&template, &values)
+            })
+        })
+        .await
+        .map_err(|e: JotError| -> CommandError { e.into() })?;
+
+    if config.capture_link_metadata {
+        spawn_link_capture(
+            app,
+            db.inner().clone(),
+            capture_vault,
+            config.write_options(),
+            &created.jot,
+        );
+    }
+    Ok(created)
+}
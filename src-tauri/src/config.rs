@@ -3,15 +3,105 @@
 // This module will be implemented in Epic 2 to allow users to configure vault paths
 
 use std::path::PathBuf;
+use std::time::Duration;
 use serde::{Deserialize, Serialize};
+use crate::jots::filenames::DEFAULT_FILENAME_TEMPLATE;
+use crate::jots::frontmatter::FrontmatterSchema;
+use crate::jots::storage::WriteOptions;
+use crate::jots::validation::ContentRules;
+
+/// Default number of days a deleted jot stays in the trash
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+/// Default interval between background jot index consistency checks
+pub const DEFAULT_CONSISTENCY_CHECK_INTERVAL_SECS: u64 = 300;
+
+/// Default interval between checks for due reminders
+pub const DEFAULT_REMINDER_CHECK_INTERVAL_SECS: u64 = 30;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AppConfig {
     pub vault_path: PathBuf,
     pub jots_folder: String,  // default: ".scribel/jots"
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    #[serde(default = "default_consistency_check_interval_secs")]
+    pub consistency_check_interval_secs: u64,
+    #[serde(default = "default_reminder_check_interval_secs")]
+    pub reminder_check_interval_secs: u64,
+    /// Move archived jot files into .scribel/jots/archive/
+    #[serde(default)]
+    pub archive_to_subfolder: bool,
+    /// Fetch title, description and excerpt of web pages linked from jots
+    #[serde(default = "default_capture_link_metadata")]
+    pub capture_link_metadata: bool,
+    /// Rules jot content must satisfy when created or edited
+    #[serde(default)]
+    pub validation: ContentRules,
+    /// File name template for new jots, e.g. "{{year}}/{{month}}/{{date}} {{first-words}}.md"
+    #[serde(default = "default_filename_template")]
+    pub filename_template: String,
+    /// Frontmatter key names, extra properties and date format (e.g. for Dataview)
+    #[serde(default)]
+    pub frontmatter: FrontmatterSchema,
+}
+
+fn default_trash_retention_days() -> u32 {
+    DEFAULT_TRASH_RETENTION_DAYS
+}
+
+fn default_consistency_check_interval_secs() -> u64 {
+    DEFAULT_CONSISTENCY_CHECK_INTERVAL_SECS
+}
+
+fn default_reminder_check_interval_secs() -> u64 {
+    DEFAULT_REMINDER_CHECK_INTERVAL_SECS
+}
+
+fn default_capture_link_metadata() -> bool {
+    true
+}
+
+fn default_filename_template() -> String {
+    DEFAULT_FILENAME_TEMPLATE.to_string()
 }
 
 impl AppConfig {
+    /// Config for the given vault with default settings
+    pub fn new(vault_path: PathBuf) -> Self {
+        Self {
+            vault_path,
+            jots_folder: String::from(".scribel/jots"),
+            trash_retention_days: DEFAULT_TRASH_RETENTION_DAYS,
+            consistency_check_interval_secs: DEFAULT_CONSISTENCY_CHECK_INTERVAL_SECS,
+            reminder_check_interval_secs: DEFAULT_REMINDER_CHECK_INTERVAL_SECS,
+            archive_to_subfolder: false,
+            capture_link_metadata: true,
+            validation: ContentRules::default(),
+            filename_template: default_filename_template(),
+            frontmatter: FrontmatterSchema::default(),
+        }
+    }
+
+    /// Interval between consistency checks (at least a second: a zero period panics the ticker)
+    pub fn consistency_check_interval(&self) -> Duration {
+        Duration::from_secs(self.consistency_check_interval_secs.max(1))
+    }
+
+    /// Interval between checks for due reminders (at least a second, like the consistency check)
+    pub fn reminder_check_interval(&self) -> Duration {
+        Duration::from_secs(self.reminder_check_interval_secs.max(1))
+    }
+
+    /// Content rules, file name template and frontmatter schema for jot writes
+    pub fn write_options(&self) -> WriteOptions {
+        WriteOptions {
+            rules: self.validation.clone(),
+            filename_template: self.filename_template.clone(),
+            frontmatter: self.frontmatter.clone(),
+        }
+    }
+
     /// Detect Obsidian vault in common locations
     /// Implementation planned for Epic 2, Feature 2.1
     pub fn detect_vault() -> Option<PathBuf> {
@@ -22,3 +112,18 @@ impl AppConfig {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_check_interval_is_clamped() {
+        let mut config = AppConfig::new(PathBuf::from("/vault"));
+        config.consistency_check_interval_secs = 0;
+        config.reminder_check_interval_secs = 0;
+
+        assert_eq!(config.consistency_check_interval(), Duration::from_secs(1));
+        assert_eq!(config.reminder_check_interval(), Duration::from_secs(1));
+    }
+}
@@ -1,4 +1,4 @@
-use rusqlite::{Connection, Result};
+use rusqlite::{Connection, OpenFlags, Result};
 use std::path::Path;
 
 /// Initialize SQLite database with WAL mode for concurrent access
@@ -14,6 +14,19 @@ pub fn init_db(db_path: &Path) -> Result<Connection> {
     Ok(conn)
 }
 
+/// Open a read-only connection to an existing database (for the reader pool)
+/// WAL mode lets these read concurrently with the writer
+pub fn open_reader(db_path: &Path) -> Result<Connection> {
+    let conn = Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+
+    conn.pragma_update(None, "query_only", true)?;
+
+    Ok(conn)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -38,4 +51,20 @@ mod tests {
             .unwrap();
         assert_eq!(sync_mode, 1); // 1 = NORMAL
     }
+
+    #[test]
+    fn test_open_reader_is_read_only() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+
+        let writer = init_db(&db_path).unwrap();
+        writer.execute("CREATE TABLE t (x INTEGER)", []).unwrap();
+
+        let reader = open_reader(&db_path).unwrap();
+        let count: i64 = reader
+            .query_row("SELECT COUNT(*) FROM t", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+        assert!(reader.execute("INSERT INTO t (x) VALUES (1)", []).is_err());
+    }
 }
@@ -1,6 +1,8 @@
 use rusqlite::{Connection, Result};
 
-pub const SCHEMA_VERSION: i32 = 1;
+/// Version the migrations below bring a database to (each step sets its own)
+#[cfg(test)]
+pub const SCHEMA_VERSION: i32 = 10;
 
 /// Run database migrations to ensure schema is up to date
 pub fn run_migrations(conn: &Connection) -> Result<()> {
@@ -8,18 +10,76 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
         .query_row("PRAGMA user_version", [], |row| row.get(0))
         .unwrap_or(0);
 
-    if version < SCHEMA_VERSION {
-        create_jot_index_table(conn)?;
-        create_embeddings_table(conn)?;
-        conn.execute(
-            &format!("PRAGMA user_version = {}", SCHEMA_VERSION),
-            [],
-        )?;
+    if version < 1 {
+        migrate(conn, 1, |conn| {
+            create_jot_index_table(conn)?;
+            create_embeddings_table(conn)
+        })?;
+    }
+
+    if version < 2 {
+        migrate(conn, 2, create_jot_revisions_table)?;
+    }
+
+    if version < 3 {
+        migrate(conn, 3, create_jot_listing_indexes)?;
+    }
+
+    if version < 4 {
+        migrate(conn, 4, create_jot_reviews_table)?;
+    }
+
+    if version < 5 {
+        migrate(conn, 5, add_jot_archived_column)?;
+    }
+
+    if version < 6 {
+        migrate(conn, 6, create_attachment_tables)?;
+    }
+
+    if version < 7 {
+        migrate(conn, 7, create_jot_links_external_table)?;
+    }
+
+    if version < 8 {
+        migrate(conn, 8, create_jot_properties_table)?;
+    }
+
+    if version < 9 {
+        migrate(conn, 9, create_jot_tasks_table)?;
+    }
+
+    if version < 10 {
+        migrate(conn, 10, create_reminders_table)?;
     }
 
     Ok(())
 }
 
+/// Run one migration step and set user_version to `version` in a single transaction
+/// An interrupted run resumes at the first step that didn't commit, never repeats one that did
+fn migrate(
+    conn: &Connection,
+    version: i32,
+    step: impl FnOnce(&Connection) -> Result<()>,
+) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    step(&tx)?;
+    tx.execute(&format!("PRAGMA user_version = {}", version), [])?;
+    tx.commit()
+}
+
+/// Whether `table` already has `column`
+/// Databases from builds that didn't commit each step may hold columns of steps they
+/// ran without recording, so adding a column checks first
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+        rusqlite::params![table, column],
+        |row| row.get(0),
+    )
+}
+
 /// Create jot_index table for fast queries
 fn create_jot_index_table(conn: &Connection) -> Result<()> {
     conn.execute_batch(
@@ -45,6 +105,238 @@ fn create_jot_index_table(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Create jot_revisions table (edit history, one row per prior version)
+fn create_jot_revisions_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS jot_revisions (
+            revision_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            jot_id TEXT NOT NULL,
+            content TEXT NOT NULL,
+            tags TEXT DEFAULT '[]',
+            links TEXT DEFAULT '[]',
+            promoted INTEGER DEFAULT 0,
+            created_at INTEGER NOT NULL,
+            modified_at INTEGER NOT NULL,
+            recorded_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_revisions_jot_id ON jot_revisions(jot_id, revision_id DESC);
+        "#,
+    )?;
+
+    Ok(())
+}
+
+/// Create indexes backing keyset pagination of jot listings
+fn create_jot_listing_indexes(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_jot_created_at_id ON jot_index(created_at, id);
+        CREATE INDEX IF NOT EXISTS idx_jot_modified_at_id ON jot_index(modified_at, id);
+        "#,
+    )?;
+
+    Ok(())
+}
+
+/// Create jot_reviews table (daily review scheduling, one row per reviewed jot)
+fn create_jot_reviews_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS jot_reviews (
+            jot_id TEXT PRIMARY KEY,
+            status TEXT NOT NULL,
+            review_count INTEGER NOT NULL DEFAULT 0,
+            interval_days INTEGER NOT NULL DEFAULT 0,
+            last_reviewed_at INTEGER NOT NULL,
+            due_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_reviews_due_at ON jot_reviews(due_at);
+        "#,
+    )?;
+
+    Ok(())
+}
+
+/// Add jot_index.archived (processed jots put away without becoming notes)
+fn add_jot_archived_column(conn: &Connection) -> Result<()> {
+    if !has_column(conn, "jot_index", "archived")? {
+        conn.execute("ALTER TABLE jot_index ADD COLUMN archived INTEGER DEFAULT 0", [])?;
+    }
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_jot_archived ON jot_index(archived)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Create attachments (files stored by Scribel) and jot_attachments (embeds in jot content)
+fn create_attachment_tables(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS attachments (
+            file_name TEXT PRIMARY KEY,
+            file_path TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            created_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS jot_attachments (
+            jot_id TEXT NOT NULL,
+            file_name TEXT NOT NULL,
+            PRIMARY KEY (jot_id, file_name)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_jot_attachments_file_name ON jot_attachments(file_name);
+        "#,
+    )?;
+
+    Ok(())
+}
+
+/// Create jot_links_external table (metadata of web pages linked from jots)
+fn create_jot_links_external_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS jot_links_external (
+            jot_id TEXT NOT NULL,
+            url TEXT NOT NULL,
+            title TEXT,
+            description TEXT,
+            canonical_url TEXT,
+            site_name TEXT,
+            excerpt TEXT,
+            fetched_at INTEGER NOT NULL,
+            PRIMARY KEY (jot_id, url)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_links_external_url ON jot_links_external(url);
+        "#,
+    )?;
+
+    Ok(())
+}
+
+/// Add jot_index.properties (custom properties as JSON) and jot_properties (one row per
+/// property value, for filtering)
+fn create_jot_properties_table(conn: &Connection) -> Result<()> {
+    if !has_column(conn, "jot_index", "properties")? {
+        conn.execute(
+            "ALTER TABLE jot_index ADD COLUMN properties TEXT DEFAULT '{}'",
+            [],
+        )?;
+    }
+
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS jot_properties (
+            jot_id TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            PRIMARY KEY (jot_id, key, value)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_jot_properties_key_value
+            ON jot_properties(key COLLATE NOCASE, value COLLATE NOCASE);
+        "#,
+    )?;
+
+    // Inline fields come from the indexed content. Frontmatter-only properties need the
+    // files: the consistency check at startup compares properties and re-indexes those jots.
+    let mut stmt = conn.prepare("SELECT id, content FROM jot_index")?;
+    let jots = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<(String, String)>>>()?;
+    for (id, content) in jots {
+        let properties = crate::jots::parser::extract_properties(&content);
+        if properties.is_empty() {
+            continue;
+        }
+
+        let properties_json =
+            serde_json::to_string(&properties).unwrap_or_else(|_| "{}".to_string());
+        conn.execute(
+            "UPDATE jot_index SET properties = ?1 WHERE id = ?2",
+            rusqlite::params![properties_json, id],
+        )?;
+        crate::jots::index::sync_jot_properties(conn, &id, &properties)?;
+    }
+
+    Ok(())
+}
+
+/// Create jot_tasks table (checkbox tasks in jot content) and fill it from indexed jots
+fn create_jot_tasks_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS jot_tasks (
+            jot_id TEXT NOT NULL,
+            line INTEGER NOT NULL,
+            text TEXT NOT NULL,
+            done INTEGER NOT NULL DEFAULT 0,
+            due TEXT,
+            priority INTEGER NOT NULL DEFAULT 2,
+            PRIMARY KEY (jot_id, line)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_jot_tasks_done_due ON jot_tasks(done, due);
+        "#,
+    )?;
+
+    let mut stmt = conn.prepare("SELECT id, content FROM jot_index")?;
+    let jots = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<(String, String)>>>()?;
+    for (id, content) in jots {
+        crate::jots::tasks::sync_tasks(conn, &id, &content)?;
+    }
+
+    Ok(())
+}
+
+/// Create reminders table (reminders found in jot content) and fill it from indexed jots
+fn create_reminders_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS reminders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            jot_id TEXT NOT NULL,
+            source TEXT NOT NULL,
+            text TEXT NOT NULL,
+            due_at INTEGER NOT NULL,
+            remind_at INTEGER NOT NULL,
+            status TEXT NOT NULL,
+            UNIQUE (jot_id, source)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_reminders_status_remind_at ON reminders(status, remind_at);
+        "#,
+    )?;
+
+    // Reminders of existing jots resolve against when each was last written
+    let now = chrono::Utc::now();
+    let mut stmt = conn.prepare("SELECT id, content, modified_at FROM jot_index")?;
+    let jots = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?
+        .collect::<Result<Vec<(String, String, i64)>>>()?;
+    for (id, content, modified_at) in jots {
+        let written_at = chrono::DateTime::from_timestamp_millis(modified_at).unwrap_or_default();
+        crate::jots::reminders::sync_reminders(conn, &id, &content, written_at, now)?;
+    }
+
+    Ok(())
+}
+
 /// Create embeddings table (for future RAG features)
 fn create_embeddings_table(conn: &Connection) -> Result<()> {
     conn.execute_batch(
@@ -100,5 +392,161 @@ mod tests {
             )
             .unwrap();
         assert_eq!(embeddings_exists, 1);
+
+        // Verify jot_revisions table exists
+        let revisions_exists: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='jot_revisions'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(revisions_exists, 1);
+
+        // Verify jot_reviews table exists
+        let reviews_exists: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='jot_reviews'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(reviews_exists, 1);
+    }
+
+    #[test]
+    fn test_failed_migration_step_rolls_back() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+
+        let result = migrate(&conn, SCHEMA_VERSION + 1, |conn| {
+            conn.execute_batch("CREATE TABLE half_done (id TEXT)")?;
+            Err(rusqlite::Error::InvalidQuery)
+        });
+        assert!(result.is_err());
+
+        let version: i32 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+        let half_done: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'half_done'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(half_done, 0);
+    }
+
+    #[test]
+    fn test_archived_column_step_can_run_again() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+
+        // The column is there but the step was never recorded
+        migrate(&conn, 5, add_jot_archived_column).unwrap();
+        assert!(has_column(&conn, "jot_index", "archived").unwrap());
+    }
+
+    #[test]
+    fn test_unrecorded_migrations_run_again() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+
+        // Columns added by steps 5 and 8 exist, but the version says step 4
+        conn.execute("PRAGMA user_version = 4", []).unwrap();
+        run_migrations(&conn).unwrap();
+
+        let version: i32 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+        assert!(has_column(&conn, "jot_index", "properties").unwrap());
+    }
+
+    #[test]
+    fn test_migrations_fill_jot_properties() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+
+        // Simulate a database indexed before custom properties existed
+        conn.execute_batch(
+            r#"
+            DROP TABLE jot_properties;
+            ALTER TABLE jot_index DROP COLUMN properties;
+            INSERT INTO jot_index (id, file_path, content, created_at, modified_at, file_mtime)
+            VALUES ('jot-a', '.scribel/jots/jot-a.md', 'Sync' || char(10) || 'project:: Apollo', 0, 0, 0);
+            PRAGMA user_version = 7;
+            "#,
+        )
+        .unwrap();
+
+        run_migrations(&conn).unwrap();
+
+        let filter = crate::jots::models::JotFilter {
+            properties: [("project".to_string(), "apollo".to_string())].into(),
+            ..Default::default()
+        };
+        let found = crate::jots::index::search_jots(&conn, "Sync", Some(&filter), 10).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].properties["project"],
+            serde_yaml::Value::from("Apollo")
+        );
+    }
+
+    #[test]
+    fn test_migrations_fill_jot_tasks() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+
+        // Simulate a database indexed before jot_tasks existed
+        conn.execute_batch(
+            r#"
+            DROP TABLE jot_tasks;
+            INSERT INTO jot_index (id, file_path, content, created_at, modified_at, file_mtime)
+            VALUES ('jot-a', '.scribel/jots/jot-a.md', 'Plan' || char(10) || '- [ ] call Bob', 0, 0, 0);
+            PRAGMA user_version = 8;
+            "#,
+        )
+        .unwrap();
+
+        run_migrations(&conn).unwrap();
+
+        let text: String = conn
+            .query_row(
+                "SELECT text FROM jot_tasks WHERE jot_id = 'jot-a' AND line = 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(text, "call Bob");
+    }
+
+    #[test]
+    fn test_migrations_from_v1() {
+        let conn = Connection::open_in_memory().unwrap();
+
+        // Simulate a database created before jot_revisions existed
+        create_jot_index_table(&conn).unwrap();
+        create_embeddings_table(&conn).unwrap();
+        conn.execute("PRAGMA user_version = 1", []).unwrap();
+
+        run_migrations(&conn).unwrap();
+
+        let version: i32 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+
+        let revisions_exists: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='jot_revisions'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(revisions_exists, 1);
     }
 }
@@ -1,6 +1,6 @@
 pub mod connection;
 pub mod migrations;
+pub mod pool;
 
-// Re-export main functions
-pub use connection::init_db;
-pub use migrations::run_migrations;
+// Re-export main types
+pub use pool::{Database, PoolError};
@@ -0,0 +1,247 @@
+use crate::db::connection::{init_db, open_reader};
+use crate::db::migrations::run_migrations;
+use rusqlite::Connection;
+use std::path::Path;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use thiserror::Error;
+
+/// Default number of read-only connections
+pub const DEFAULT_READERS: usize = 4;
+
+/// Errors from the database access layer itself (not from queries)
+#[derive(Error, Debug)]
+pub enum PoolError {
+    #[error("Database lock poisoned")]
+    Poisoned,
+
+    #[error("Database task failed: {0}")]
+    TaskFailed(String),
+
+    #[error("Database error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+}
+
+/// Idle read-only connections
+struct ReaderPool {
+    idle: Mutex<Vec<Connection>>,
+    available: Condvar,
+}
+
+impl ReaderPool {
+    /// Take an idle connection, waiting for one if all are in use
+    /// The idle list is only pushed to and popped from, so a poisoned lock is still usable
+    fn checkout(&self) -> PooledReader<'_> {
+        let mut idle = self.idle.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(conn) = idle.pop() {
+                return PooledReader {
+                    pool: self,
+                    conn: Some(conn),
+                };
+            }
+            idle = self
+                .available
+                .wait(idle)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+/// Reader checked out of the pool; goes back when dropped
+struct PooledReader<'a> {
+    pool: &'a ReaderPool,
+    conn: Option<Connection>,
+}
+
+impl Drop for PooledReader<'_> {
+    fn drop(&mut self) {
+        // Always return the connection: dropping it would leave waiting readers stuck
+        if let Some(conn) = self.conn.take() {
+            let mut idle = self
+                .pool
+                .idle
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            idle.push(conn);
+            self.pool.available.notify_one();
+        }
+    }
+}
+
+/// SQLite access for commands: one writer connection and a pool of readers
+///
+/// Queries run on Tokio's blocking thread pool (spawn_blocking), so they never stall
+/// the async runtime. Writes are serialized through the single writer; thanks to WAL
+/// mode, reads proceed on their own connections while a write (or reindex) is running.
+#[derive(Clone)]
+pub struct Database {
+    writer: Arc<Mutex<Connection>>,
+    readers: Arc<ReaderPool>,
+}
+
+impl Database {
+    /// Open the database at `db_path`, run migrations and open `readers` read-only connections
+    pub fn open(db_path: &Path, readers: usize) -> Result<Self, PoolError> {
+        let writer = init_db(db_path)?;
+        run_migrations(&writer)?;
+
+        let idle = (0..readers.max(1))
+            .map(|_| open_reader(db_path))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            writer: Arc::new(Mutex::new(writer)),
+            readers: Arc::new(ReaderPool {
+                idle: Mutex::new(idle),
+                available: Condvar::new(),
+            }),
+        })
+    }
+
+    /// Run a read-only query on a pooled reader connection
+    pub async fn read<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&Connection) -> Result<T, E> + Send + 'static,
+        T: Send + 'static,
+        E: From<PoolError> + Send + 'static,
+    {
+        let readers = Arc::clone(&self.readers);
+        tokio::task::spawn_blocking(move || {
+            let mut reader = readers.checkout();
+            let conn = reader.conn.as_mut().ok_or(PoolError::Poisoned)?;
+            f(conn)
+        })
+        .await
+        .map_err(|e| E::from(PoolError::TaskFailed(e.to_string())))?
+    }
+
+    /// Run a write on the single writer connection
+    pub async fn write<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut Connection) -> Result<T, E> + Send + 'static,
+        T: Send + 'static,
+        E: From<PoolError> + Send + 'static,
+    {
+        let writer = Arc::clone(&self.writer);
+        tokio::task::spawn_blocking(move || {
+            let mut conn = writer.lock().map_err(|_| PoolError::Poisoned)?;
+            f(&mut conn)
+        })
+        .await
+        .map_err(|e| E::from(PoolError::TaskFailed(e.to_string())))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use tempfile::tempdir;
+
+    fn count_jots(conn: &Connection) -> Result<i64, PoolError> {
+        Ok(conn.query_row("SELECT COUNT(*) FROM jot_index", [], |row| row.get(0))?)
+    }
+
+    fn insert_row(conn: &Connection, id: &str) -> Result<(), PoolError> {
+        conn.execute(
+            "INSERT INTO jot_index (id, file_path, content, created_at, modified_at, file_mtime)
+             VALUES (?1, ?1, 'content', 0, 0, 0)",
+            rusqlite::params![id],
+        )?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_then_read() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db"), 2).unwrap();
+
+        db.write(|conn| insert_row(conn, "jot-1")).await.unwrap();
+
+        let count = db.read(count_jots).await.unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_readers_are_read_only() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db"), 1).unwrap();
+
+        let result = db.read(|conn| insert_row(conn, "jot-1")).await;
+        assert!(matches!(result, Err(PoolError::Sqlite(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_reads_not_blocked_by_writer() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db"), 2).unwrap();
+
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+
+        // Long-running write holding the writer inside an open transaction
+        let writer = db.clone();
+        let write = tokio::spawn(async move {
+            writer
+                .write(move |conn| {
+                    let tx = conn.transaction()?;
+                    insert_row(&tx, "jot-1")?;
+                    started_tx.send(()).unwrap();
+                    release_rx.recv().unwrap();
+                    tx.commit()?;
+                    Ok::<_, PoolError>(())
+                })
+                .await
+        });
+
+        tokio::task::spawn_blocking(move || started_rx.recv().unwrap())
+            .await
+            .unwrap();
+
+        // Readers still see the last committed state instead of waiting
+        assert_eq!(db.read(count_jots).await.unwrap(), 0);
+
+        release_tx.send(()).unwrap();
+        write.await.unwrap().unwrap();
+        assert_eq!(db.read(count_jots).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_readers_survive_poisoned_pool_lock() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db"), 1).unwrap();
+
+        let readers = Arc::clone(&db.readers);
+        std::thread::spawn(move || {
+            let _idle = readers.idle.lock().unwrap();
+            panic!("poison the reader pool");
+        })
+        .join()
+        .unwrap_err();
+        assert!(db.readers.idle.is_poisoned());
+
+        // The single reader keeps going back to the pool
+        for _ in 0..3 {
+            assert_eq!(db.read(count_jots).await.unwrap(), 0);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_reader_pool_shared_across_tasks() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db"), 1).unwrap();
+        db.write(|conn| insert_row(conn, "jot-1")).await.unwrap();
+
+        // More concurrent reads than readers: they queue for a connection
+        let reads: Vec<_> = (0..8)
+            .map(|_| {
+                let db = db.clone();
+                tokio::spawn(async move { db.read(count_jots).await })
+            })
+            .collect();
+
+        for read in reads {
+            assert_eq!(read.await.unwrap().unwrap(), 1);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use rand::Rng;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Suffix of temp files created by write_atomic
+const TEMP_SUFFIX: &str = ".tmp";
+
+/// Temp file path for a write to `path`: ".<file name>.<XXXX>.tmp" in the same directory
+/// Same directory keeps the final rename on one filesystem (atomic)
+fn temp_path_for(path: &Path) -> io::Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid file name"))?;
+    let hex: String = (0..4)
+        .map(|_| format!("{:x}", rand::thread_rng().gen_range(0..16)))
+        .collect();
+
+    Ok(path.with_file_name(format!(".{}.{}{}", file_name, hex, TEMP_SUFFIX)))
+}
+
+/// If `path` is a temp file left by write_atomic, return the file name it was meant to replace
+/// Input: ".jot-2025-01-19-143256-a1b2.md.3f9c.tmp"
+/// Output: Some("jot-2025-01-19-143256-a1b2.md")
+pub fn temp_target_name(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let inner = name.strip_prefix('.')?.strip_suffix(TEMP_SUFFIX)?;
+    let (target, hex) = inner.rsplit_once('.')?;
+
+    if target.is_empty() || hex.len() != 4 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Some(target.to_string())
+}
+
+/// Crash-safe replacement for fs::write
+/// Writes to a temp file in the same directory, fsyncs it, then renames it over `path`,
+/// so readers see either the old file or the complete new one, never a truncated file
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    write_atomic_with(path, contents, |from, to| fs::rename(from, to))
+}
+
+/// write_atomic with the final rename step supplied by the caller (tests inject failures)
+fn write_atomic_with(
+    path: &Path,
+    contents: &[u8],
+    rename: impl FnOnce(&Path, &Path) -> io::Result<()>,
+) -> io::Result<()> {
+    let temp_path = temp_path_for(path)?;
+
+    let result = (|| {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        rename(&temp_path, path)?;
+        sync_parent_dir(path)
+    })();
+
+    if result.is_err() && temp_path.exists() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    result
+}
+
+/// Persist the rename itself (directory entry) on platforms that support it
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => File::open(parent)?.sync_all(),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("jot.md");
+
+        write_atomic(&path, b"first").unwrap();
+        write_atomic(&path, b"second").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+
+        // No temp files left behind
+        let entries: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_write_atomic_failure_keeps_original() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("jot.md");
+        fs::write(&path, "original").unwrap();
+
+        // The new bytes are written and synced, then replacing the file fails
+        let result = write_atomic_with(&path, b"new", |_, _| {
+            Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "rename failed",
+            ))
+        });
+        assert!(result.is_err());
+
+        assert_eq!(fs::read(&path).unwrap(), b"original");
+        let entries: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_temp_target_name() {
+        let temp = temp_path_for(Path::new("/vault/jot-1.md")).unwrap();
+        assert_eq!(temp_target_name(&temp), Some("jot-1.md".to_string()));
+
+        assert_eq!(temp_target_name(Path::new("jot-1.md")), None);
+        assert_eq!(temp_target_name(Path::new(".jot-1.md.zzzz.tmp")), None);
+        assert_eq!(temp_target_name(Path::new("jot-1.md.3f9c.tmp")), None);
+    }
+}
@@ -0,0 +1,391 @@
+use crate::jots::atomic;
+use crate::jots::frontmatter::FrontmatterSchema;
+use crate::jots::models::{Attachment, JotError};
+use crate::jots::parser::extract_embeds;
+use crate::jots::storage::JOTS_DIR;
+use crate::jots::trash;
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, Result as SqlResult};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Largest attachment accepted (25 MB)
+pub const MAX_ATTACHMENT_BYTES: usize = 25 * 1024 * 1024;
+
+/// Obsidian settings file holding the attachment folder
+const OBSIDIAN_APP_SETTINGS: &str = ".obsidian/app.json";
+
+/// Folders never searched for notes, besides hidden ones (.git, .obsidian, .scribel, ...)
+const SKIPPED_FOLDERS: [&str; 1] = ["node_modules"];
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct ObsidianAppSettings {
+    attachment_folder_path: Option<String>,
+}
+
+/// Vault-relative folder new attachments go to, following Obsidian's `attachmentFolderPath`
+/// "/" (Obsidian's default): vault root; "./" or "./sub": next to the jot files; otherwise that folder
+pub fn attachment_folder(vault_path: &Path) -> String {
+    let setting = fs::read_to_string(vault_path.join(OBSIDIAN_APP_SETTINGS))
+        .ok()
+        .and_then(|json| serde_json::from_str::<ObsidianAppSettings>(&json).ok())
+        .and_then(|settings| settings.attachment_folder_path)
+        .unwrap_or_else(|| "/".to_string());
+
+    let folder = if setting == "." || setting == "./" {
+        JOTS_DIR.to_string()
+    } else if let Some(sub) = setting.strip_prefix("./") {
+        format!("{}/{}", JOTS_DIR, sub.trim_matches('/'))
+    } else {
+        setting.trim_matches('/').to_string()
+    };
+
+    // Never write outside the vault, whatever the settings file says
+    let inside_vault = Path::new(&folder)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)));
+    if inside_vault {
+        folder
+    } else {
+        String::new()
+    }
+}
+
+/// File name safe to embed: no directories, and none of the characters that break ![[links]]
+fn sanitize_file_name(file_name: &str) -> Result<String, JotError> {
+    let base = Path::new(file_name.trim())
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let sanitized: String = base
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | '|' | '#' | '^' | ':' | '\\' => ' ',
+            _ => c,
+        })
+        .collect();
+    let sanitized = sanitized.trim().to_string();
+
+    if sanitized.is_empty() || sanitized.starts_with('.') {
+        return Err(JotError::validation(
+            "file_name",
+            "Attachment needs a file name",
+        ));
+    }
+    Ok(sanitized)
+}
+
+fn attachment_exists(conn: &Connection, file_name: &str) -> Result<bool, JotError> {
+    let found = conn
+        .query_row(
+            "SELECT 1 FROM attachments WHERE file_name = ?1",
+            rusqlite::params![file_name],
+            |_| Ok(()),
+        )
+        .optional()?;
+    Ok(found.is_some())
+}
+
+/// `name`, or "stem 1.ext", "stem 2.ext", ... if taken (like Obsidian does)
+fn unique_file_name(conn: &Connection, dir: &Path, name: &str) -> Result<String, JotError> {
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{}", extension)),
+        _ => (name, String::new()),
+    };
+
+    let mut candidate = name.to_string();
+    let mut n = 1;
+    while dir.join(&candidate).exists() || attachment_exists(conn, &candidate)? {
+        candidate = format!("{} {}{}", stem, n, extension);
+        n += 1;
+    }
+    Ok(candidate)
+}
+
+/// Write an attachment file into the attachment folder under a unique name
+/// Only the file is written; see insert_attachment for the index row
+pub fn write_attachment(
+    conn: &Connection,
+    vault_path: &Path,
+    file_name: &str,
+    bytes: &[u8],
+) -> Result<Attachment, JotError> {
+    if bytes.is_empty() {
+        return Err(JotError::validation("data", "Attachment is empty"));
+    }
+    if bytes.len() > MAX_ATTACHMENT_BYTES {
+        return Err(JotError::validation("data", "Attachment exceeds 25 MB"));
+    }
+
+    let folder = attachment_folder(vault_path);
+    let dir = vault_path.join(&folder);
+    fs::create_dir_all(&dir)?;
+
+    let file_name = unique_file_name(conn, &dir, &sanitize_file_name(file_name)?)?;
+    atomic::write_atomic(&dir.join(&file_name), bytes)?;
+
+    let file_path = if folder.is_empty() {
+        file_name.clone()
+    } else {
+        format!("{}/{}", folder, file_name)
+    };
+
+    Ok(Attachment {
+        file_name,
+        file_path,
+        size: bytes.len() as u64,
+        created_at: Utc::now(),
+    })
+}
+
+/// Record a stored attachment in the attachments table
+pub fn insert_attachment(conn: &Connection, attachment: &Attachment) -> Result<(), JotError> {
+    conn.execute(
+        "INSERT INTO attachments (file_name, file_path, size, created_at) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![
+            attachment.file_name,
+            attachment.file_path,
+            attachment.size as i64,
+            attachment.created_at.timestamp_millis(),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Attachments embedded by a jot, in file name order
+pub fn get_jot_attachments(conn: &Connection, jot_id: &str) -> Result<Vec<Attachment>, JotError> {
+    let mut stmt = conn.prepare(
+        "SELECT a.file_name, a.file_path, a.size, a.created_at
+         FROM attachments a
+         JOIN jot_attachments j ON j.file_name = a.file_name
+         WHERE j.jot_id = ?1
+         ORDER BY a.file_name",
+    )?;
+
+    let attachments = stmt
+        .query_map(rusqlite::params![jot_id], |row| {
+            let size: i64 = row.get(2)?;
+            let created_at_ms: i64 = row.get(3)?;
+            Ok(Attachment {
+                file_name: row.get(0)?,
+                file_path: row.get(1)?,
+                size: size as u64,
+                created_at: DateTime::from_timestamp_millis(created_at_ms).unwrap_or_default(),
+            })
+        })?
+        .collect::<SqlResult<Vec<Attachment>>>()?;
+
+    Ok(attachments)
+}
+
+/// Markdown notes under `dir`, skipping hidden and dependency folders
+/// Symlinks are not followed
+fn collect_notes(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), JotError> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') {
+            continue;
+        }
+
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if !SKIPPED_FOLDERS.contains(&name.as_ref()) {
+                collect_notes(&entry.path(), files)?;
+            }
+        } else if file_type.is_file() && name.ends_with(".md") {
+            files.push(entry.path());
+        }
+    }
+
+    Ok(())
+}
+
+/// File names embedded by notes elsewhere in the vault (e.g. notes jots were promoted to)
+/// Walks the vault, so call it before taking the writer lock and pass it to collect_garbage
+pub fn embedded_in_notes(vault_path: &Path) -> Result<HashSet<String>, JotError> {
+    let mut files = Vec::new();
+    collect_notes(vault_path, &mut files)?;
+
+    let mut embedded = HashSet::new();
+    for file in files {
+        if let Ok(content) = fs::read_to_string(&file) {
+            embedded.extend(extract_embeds(&content));
+        }
+    }
+
+    Ok(embedded)
+}
+
+/// Attachments no indexed jot embeds
+fn orphaned_attachments(conn: &Connection) -> Result<Vec<(String, String)>, JotError> {
+    let mut stmt = conn.prepare(
+        "SELECT file_name, file_path FROM attachments a
+         WHERE NOT EXISTS (SELECT 1 FROM jot_attachments j WHERE j.file_name = a.file_name)
+         ORDER BY file_name",
+    )?;
+    let orphans = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<SqlResult<Vec<(String, String)>>>()?;
+    Ok(orphans)
+}
+
+/// Whether any attachment is no longer embedded by an indexed jot (worth a garbage collection)
+pub fn has_orphans(conn: &Connection) -> Result<bool, JotError> {
+    Ok(!orphaned_attachments(conn)?.is_empty())
+}
+
+/// Delete attachments no jot embeds any more; returns their file names
+/// Attachments still embedded by trashed jots or vault notes (`notes`, see embedded_in_notes)
+/// are kept
+pub fn collect_garbage(
+    conn: &Connection,
+    vault_path: &Path,
+    schema: &FrontmatterSchema,
+    notes: &HashSet<String>,
+) -> Result<Vec<String>, JotError> {
+    let orphans = orphaned_attachments(conn)?;
+    if orphans.is_empty() {
+        return Ok(vec![]);
+    }
+
+    // Trashed jots are read under the lock: a jot deleted since the note scan still counts
+    let mut trashed: HashSet<String> = HashSet::new();
+    for jot in trash::list_trash(vault_path, schema)? {
+        trashed.extend(extract_embeds(&jot.jot.content));
+    }
+
+    let mut removed = Vec::new();
+    for (file_name, file_path) in orphans {
+        if trashed.contains(&file_name) || notes.contains(&file_name) {
+            continue;
+        }
+
+        match fs::remove_file(vault_path.join(&file_path)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        conn.execute(
+            "DELETE FROM attachments WHERE file_name = ?1",
+            rusqlite::params![file_name],
+        )?;
+        removed.push(file_name);
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::test_support::setup;
+    use crate::jots::{index, unit_of_work};
+    use tempfile::tempdir;
+
+    fn set_attachment_folder(vault_path: &Path, setting: &str) {
+        fs::create_dir_all(vault_path.join(".obsidian")).unwrap();
+        fs::write(
+            vault_path.join(OBSIDIAN_APP_SETTINGS),
+            format!(r#"{{"attachmentFolderPath": "{}"}}"#, setting),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_attachment_folder_follows_obsidian_setting() {
+        let vault = tempdir().unwrap();
+        assert_eq!(attachment_folder(vault.path()), "");
+
+        set_attachment_folder(vault.path(), "Attachments/Images");
+        assert_eq!(attachment_folder(vault.path()), "Attachments/Images");
+
+        set_attachment_folder(vault.path(), "./assets");
+        assert_eq!(attachment_folder(vault.path()), ".scribel/jots/assets");
+
+        set_attachment_folder(vault.path(), "../outside");
+        assert_eq!(attachment_folder(vault.path()), "");
+    }
+
+    #[test]
+    fn test_add_attachment_embeds_with_unique_name() {
+        let (vault, mut conn) = setup();
+        set_attachment_folder(vault.path(), "Attachments");
+
+        let (first, second) = unit_of_work::run(&mut conn, vault.path(), |uow| {
+            let jot = uow.create_jot("Screenshot of the bug")?;
+            let first = uow.add_attachment(&jot.id, "shot.png", b"png-1")?;
+            let second = uow.add_attachment(&jot.id, "../shot.png", b"png-2")?;
+            Ok((first, second))
+        })
+        .unwrap();
+
+        assert_eq!(first.content, "Screenshot of the bug\n\n![[shot.png]]");
+        assert!(second.content.ends_with("![[shot.png]]\n\n![[shot 1.png]]"));
+        assert_eq!(
+            fs::read(vault.path().join("Attachments/shot 1.png")).unwrap(),
+            b"png-2"
+        );
+
+        let attachments = get_jot_attachments(&conn, &second.id).unwrap();
+        let names: Vec<&str> = attachments.iter().map(|a| a.file_name.as_str()).collect();
+        assert_eq!(names, vec!["shot 1.png", "shot.png"]);
+    }
+
+    #[test]
+    fn test_garbage_collection_keeps_embedded_attachments() {
+        let (vault, mut conn) = setup();
+
+        let (kept, dropped) = unit_of_work::run(&mut conn, vault.path(), |uow| {
+            let kept = uow.create_jot("Kept")?;
+            let kept = uow.add_attachment(&kept.id, "kept.png", b"1")?;
+            let dropped = uow.create_jot("Dropped")?;
+            let dropped = uow.add_attachment(&dropped.id, "dropped.png", b"2")?;
+            Ok((kept, dropped))
+        })
+        .unwrap();
+
+        // Deleted jots sit in the trash and can come back: their attachments stay
+        unit_of_work::run(&mut conn, vault.path(), |uow| uow.delete_jot(&dropped.id)).unwrap();
+        let notes = embedded_in_notes(vault.path()).unwrap();
+        assert!(
+            collect_garbage(&conn, vault.path(), &FrontmatterSchema::default(), &notes)
+                .unwrap()
+                .is_empty()
+        );
+
+        trash::purge_jot(vault.path(), &dropped.id).unwrap();
+        assert!(has_orphans(&conn).unwrap());
+        let notes = embedded_in_notes(vault.path()).unwrap();
+        assert_eq!(
+            collect_garbage(&conn, vault.path(), &FrontmatterSchema::default(), &notes).unwrap(),
+            vec!["dropped.png"]
+        );
+        assert!(!vault.path().join("dropped.png").exists());
+        assert!(vault.path().join("kept.png").exists());
+        assert_eq!(get_jot_attachments(&conn, &kept.id).unwrap().len(), 1);
+        assert!(index::get_jot(&conn, &kept.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_embedded_in_notes_skips_hidden_folders_and_symlinks() {
+        let vault = tempdir().unwrap();
+        fs::create_dir_all(vault.path().join("Projects")).unwrap();
+        fs::write(vault.path().join("Projects/Plan.md"), "![[plan.png]]").unwrap();
+        for dir in [".git", ".scribel/trash", "node_modules/pkg"] {
+            fs::create_dir_all(vault.path().join(dir)).unwrap();
+            fs::write(vault.path().join(dir).join("Note.md"), "![[hidden.png]]").unwrap();
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(vault.path(), vault.path().join("Projects/loop")).unwrap();
+
+        let embedded = embedded_in_notes(vault.path()).unwrap();
+        assert_eq!(embedded, HashSet::from(["plan.png".to_string()]));
+    }
+}
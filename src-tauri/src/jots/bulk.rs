@@ -0,0 +1,214 @@
+use crate::jots::models::{BulkOperation, BulkTarget, JotError, UpdateJotInput};
+use crate::jots::unit_of_work::UnitOfWork;
+use crate::jots::{index, parser};
+use rusqlite::Connection;
+use std::path::Path;
+
+/// Most jots a query target may match
+pub const MAX_QUERY_TARGETS: u32 = 1000;
+
+/// IDs of the jots a bulk target refers to
+/// Query targets match content the same way search_jots does; a query matching
+/// more than MAX_QUERY_TARGETS jots is rejected rather than cut off
+pub fn resolve_target(conn: &Connection, target: &BulkTarget) -> Result<Vec<String>, JotError> {
+    match target {
+        BulkTarget::Ids(ids) => Ok(ids.clone()),
+        BulkTarget::Query(query) => {
+            let jots = index::search_jots(conn, query, None, MAX_QUERY_TARGETS + 1)?;
+            if jots.len() > MAX_QUERY_TARGETS as usize {
+                return Err(JotError::validation(
+                    "target",
+                    format!("Query matches more than {} jots", MAX_QUERY_TARGETS),
+                ));
+            }
+            Ok(jots.into_iter().map(|jot| jot.id).collect())
+        }
+    }
+}
+
+/// Check operation arguments once, before touching any jot
+pub fn validate(operation: &BulkOperation) -> Result<BulkOperation, JotError> {
+    match operation {
+        BulkOperation::AddTag { tag } => Ok(BulkOperation::AddTag {
+            tag: parser::normalize_tag(tag)?,
+        }),
+        BulkOperation::RemoveTag { tag } => Ok(BulkOperation::RemoveTag {
+            tag: parser::normalize_tag(tag)?,
+        }),
+        BulkOperation::Export { destination } if !Path::new(destination).is_absolute() => Err(
+            JotError::validation("destination", "Export destination must be an absolute path"),
+        ),
+        _ => Ok(operation.clone()),
+    }
+}
+
+/// Rewrite jot content with `edit`, skipping the write if nothing changed
+fn edit_content(
+    uow: &mut UnitOfWork,
+    id: &str,
+    edit: impl FnOnce(&str) -> String,
+) -> Result<(), JotError> {
+    let jot = uow.read_jot(id)?;
+    let content = edit(&jot.content);
+    if content == jot.content {
+        return Ok(());
+    }
+
+    uow.update_jot(&UpdateJotInput {
+        id: id.to_string(),
+        content,
+        base_modified_at: None,
+        base_content: None,
+    })?;
+    Ok(())
+}
+
+/// Apply a (validated) bulk operation to one jot
+pub fn apply(uow: &mut UnitOfWork, id: &str, operation: &BulkOperation) -> Result<(), JotError> {
+    match operation {
+        BulkOperation::Delete => uow.delete_jot(id),
+        BulkOperation::Promote => uow.set_promoted(id, true).map(|_| ()),
+        BulkOperation::Unpromote => uow.set_promoted(id, false).map(|_| ()),
+        BulkOperation::AddTag { tag } => {
+            edit_content(uow, id, |content| parser::add_tag(content, tag))
+        }
+        BulkOperation::RemoveTag { tag } => {
+            edit_content(uow, id, |content| parser::remove_tag(content, tag))
+        }
+        BulkOperation::MoveToFolder { folder } => uow.move_jot(id, folder).map(|_| ()),
+        BulkOperation::Export { destination } => {
+            uow.export_jot(id, Path::new(destination)).map(|_| ())
+        }
+    }
+}
+
+/// Apply an operation to every jot of `ids` within one unit of work
+/// Each jot succeeds or fails on its own; failures don't stop the rest
+pub fn run(
+    uow: &mut UnitOfWork,
+    ids: &[String],
+    operation: &BulkOperation,
+) -> Vec<(String, Result<(), JotError>)> {
+    ids.iter()
+        .map(|id| {
+            let result = uow.isolated(|uow| apply(uow, id, operation));
+            (id.clone(), result)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::test_support::setup;
+    use crate::jots::{storage, unit_of_work};
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn run_bulk(
+        conn: &mut Connection,
+        vault_path: &Path,
+        ids: &[String],
+        operation: BulkOperation,
+    ) -> Vec<(String, Result<(), JotError>)> {
+        let operation = validate(&operation).unwrap();
+        unit_of_work::run(conn, vault_path, |uow| Ok(run(uow, ids, &operation))).unwrap()
+    }
+
+    #[test]
+    fn test_bulk_add_tag_reports_missing_jots() {
+        let (vault, mut conn) = setup();
+        let jot = unit_of_work::run(&mut conn, vault.path(), |uow| uow.create_jot("Idea")).unwrap();
+
+        let ids = vec![jot.id.clone(), "jot-missing".to_string()];
+        let results = run_bulk(
+            &mut conn,
+            vault.path(),
+            &ids,
+            BulkOperation::AddTag {
+                tag: "#work".to_string(),
+            },
+        );
+
+        assert!(results[0].1.is_ok());
+        assert!(matches!(results[1].1, Err(JotError::NotFound(_))));
+
+        let indexed = index::get_jot(&conn, &jot.id).unwrap().unwrap();
+        assert_eq!(indexed.content, "Idea #work");
+        assert_eq!(indexed.tags, vec!["work"]);
+    }
+
+    #[test]
+    fn test_bulk_by_query_promotes_matches() {
+        let (vault, mut conn) = setup();
+        let (meeting, other) = unit_of_work::run(&mut conn, vault.path(), |uow| {
+            Ok((
+                uow.create_jot("Meeting notes")?,
+                uow.create_jot("Groceries")?,
+            ))
+        })
+        .unwrap();
+
+        let ids = resolve_target(&conn, &BulkTarget::Query("meeting".to_string())).unwrap();
+        assert_eq!(ids, vec![meeting.id.clone()]);
+
+        run_bulk(&mut conn, vault.path(), &ids, BulkOperation::Promote);
+        let promoted = |id: &str| index::get_jot(&conn, id).unwrap().unwrap().promoted;
+        assert!(promoted(&meeting.id));
+        assert!(!promoted(&other.id));
+    }
+
+    #[test]
+    fn test_bulk_move_and_export() {
+        let (vault, mut conn) = setup();
+        let export = tempdir().unwrap();
+        let jot = unit_of_work::run(&mut conn, vault.path(), |uow| uow.create_jot("Idea")).unwrap();
+        let ids = vec![jot.id.clone()];
+
+        run_bulk(
+            &mut conn,
+            vault.path(),
+            &ids,
+            BulkOperation::MoveToFolder {
+                folder: "ideas".to_string(),
+            },
+        );
+        let indexed = index::get_jot(&conn, &jot.id).unwrap().unwrap();
+        assert_eq!(
+            indexed.file_path,
+            format!("{}/ideas/{}.md", storage::JOTS_DIR, jot.id)
+        );
+
+        let export_op = BulkOperation::Export {
+            destination: export.path().to_string_lossy().to_string(),
+        };
+        let results = run_bulk(&mut conn, vault.path(), &ids, export_op.clone());
+        assert!(results[0].1.is_ok());
+        let exported = export.path().join(format!("{}.md", jot.id));
+        assert!(exported.exists());
+
+        // A second export refuses to overwrite the earlier copy
+        fs::write(&exported, "edited elsewhere").unwrap();
+        let results = run_bulk(&mut conn, vault.path(), &ids, export_op);
+        assert!(matches!(
+            results[0].1,
+            Err(JotError::ValidationError { .. })
+        ));
+        assert_eq!(fs::read_to_string(&exported).unwrap(), "edited elsewhere");
+    }
+
+    #[test]
+    fn test_bulk_export_removed_on_rollback() {
+        let (vault, mut conn) = setup();
+        let export = tempdir().unwrap();
+        let jot = unit_of_work::run(&mut conn, vault.path(), |uow| uow.create_jot("Idea")).unwrap();
+
+        let result: Result<(), JotError> = unit_of_work::run(&mut conn, vault.path(), |uow| {
+            uow.export_jot(&jot.id, export.path())?;
+            Err(JotError::Conflict(jot.id.clone()))
+        });
+
+        assert!(result.is_err());
+        assert!(!export.path().join(format!("{}.md", jot.id)).exists());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::jots::models::{Jot, JotError, MergeOriginals};
+use crate::jots::unit_of_work::UnitOfWork;
+use crate::jots::{ids, parser};
+use chrono::Utc;
+
+/// Merge jots into one, in chronological order
+/// The merged jot keeps the earliest created_at; its tags and links are the union of the
+/// originals', in order of first appearance
+pub fn merge_jots(
+    uow: &mut UnitOfWork,
+    ids: &[String],
+    originals: MergeOriginals,
+) -> Result<Jot, JotError> {
+    let mut unique_ids: Vec<&String> = Vec::new();
+    for id in ids {
+        if !unique_ids.contains(&id) {
+            unique_ids.push(id);
+        }
+    }
+    if unique_ids.len() < 2 {
+        return Err(JotError::validation(
+            "ids",
+            "Select at least two jots to merge",
+        ));
+    }
+
+    let mut jots = unique_ids
+        .iter()
+        .map(|id| uow.read_jot(id))
+        .collect::<Result<Vec<Jot>, JotError>>()?;
+    jots.sort_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then_with(|| ids::compare(&a.id, &b.id))
+    });
+
+    let mut content = jots
+        .iter()
+        .map(|jot| jot.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    if originals == MergeOriginals::Link {
+        let links: Vec<String> = jots.iter().map(|jot| format!("[[{}]]", jot.id)).collect();
+        content = format!("{}\n\nMerged from {}", content, links.join(", "));
+    }
+
+    let merged = uow.create_merged_jot(&content, jots[0].created_at, Utc::now())?;
+
+    if originals == MergeOriginals::Trash {
+        for jot in &jots {
+            uow.delete_jot(&jot.id)?;
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Split content at character offsets, or at blank lines if there are none
+/// Parts are trimmed; empty parts are dropped
+pub fn split_content(content: &str, offsets: &[usize]) -> Result<Vec<String>, JotError> {
+    let parts: Vec<&str> = if offsets.is_empty() {
+        let mut parts = Vec::new();
+        let mut start = 0;
+        let mut position = 0;
+        for line in content.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                parts.push(&content[start..position]);
+                start = position + line.len();
+            }
+            position += line.len();
+        }
+        parts.push(&content[start..]);
+        parts
+    } else {
+        let char_count = content.chars().count();
+        let mut offsets = offsets.to_vec();
+        offsets.sort_unstable();
+        offsets.dedup();
+        if offsets.last().is_some_and(|&offset| offset > char_count) {
+            return Err(JotError::validation(
+                "offsets",
+                format!(
+                    "Offsets must be within the content ({} characters)",
+                    char_count
+                ),
+            ));
+        }
+
+        // Character offsets to byte positions
+        let mut bounds: Vec<usize> = offsets
+            .iter()
+            .map(|&offset| {
+                content
+                    .char_indices()
+                    .nth(offset)
+                    .map_or(content.len(), |(byte, _)| byte)
+            })
+            .collect();
+        bounds.insert(0, 0);
+        bounds.push(content.len());
+        bounds.windows(2).map(|w| &content[w[0]..w[1]]).collect()
+    };
+
+    Ok(parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Split a jot into several, then move the original to the trash
+/// Each part keeps the original's created_at and tags (appended if the part lacks them)
+pub fn split_jot(uow: &mut UnitOfWork, id: &str, offsets: &[usize]) -> Result<Vec<Jot>, JotError> {
+    let original = uow.read_jot(id)?;
+
+    let parts = split_content(&original.content, offsets)?;
+    if parts.len() < 2 {
+        return Err(JotError::validation(
+            "offsets",
+            "Split must produce at least two non-empty jots",
+        ));
+    }
+
+    let mut jots = Vec::with_capacity(parts.len());
+    for part in parts {
+        let content = original
+            .tags
+            .iter()
+            .fold(part, |content, tag| parser::add_tag(&content, tag));
+        jots.push(uow.create_jot_at(&content, original.created_at, Utc::now())?);
+    }
+
+    uow.delete_jot(id)?;
+    Ok(jots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::frontmatter::FrontmatterSchema;
+    use crate::jots::test_support::setup;
+    use crate::jots::{index, storage, trash, unit_of_work};
+    use chrono::Duration;
+
+    #[test]
+    fn test_split_content() {
+        let content = "First idea #a\n\nSecond idea\n  \nThird";
+        assert_eq!(
+            split_content(content, &[]).unwrap(),
+            vec!["First idea #a", "Second idea", "Third"]
+        );
+        assert_eq!(
+            split_content("héllo world", &[5]).unwrap(),
+            vec!["héllo", "world"]
+        );
+        assert!(split_content("short", &[10]).is_err());
+    }
+
+    #[test]
+    fn test_merge_in_chronological_order() {
+        let (vault, mut conn) = setup();
+        let older = Utc::now() - Duration::days(2);
+
+        let merged = unit_of_work::run(&mut conn, vault.path(), |uow| {
+            let newer = uow.create_jot("Second part #work")?;
+            let first = uow.create_jot_at("First part #work [[Project]]", older, older)?;
+            let ids = vec![newer.id, first.id];
+            merge_jots(uow, &ids, MergeOriginals::Trash)
+        })
+        .unwrap();
+
+        assert_eq!(
+            merged.content,
+            "First part #work [[Project]]\n\nSecond part #work"
+        );
+        assert_eq!(merged.created_at, older);
+        assert_eq!(merged.tags, vec!["work"]);
+        assert_eq!(merged.links, vec!["Project"]);
+        let indexed = index::get_jot(&conn, &merged.id).unwrap().unwrap();
+        assert_eq!(indexed.tags, vec!["work"]);
+        let file =
+            storage::read_jot(vault.path(), &merged.id, &FrontmatterSchema::default()).unwrap();
+        assert_eq!(file.tags, vec!["work"]);
+
+        assert_eq!(index::get_all_jots(&conn).unwrap().len(), 1);
+        assert_eq!(
+            trash::list_trash(vault.path(), &FrontmatterSchema::default())
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_merge_linking_keeps_originals() {
+        let (vault, mut conn) = setup();
+
+        let (merged, ids) = unit_of_work::run(&mut conn, vault.path(), |uow| {
+            let ids = vec![uow.create_jot("One")?.id, uow.create_jot("Two")?.id];
+            let merged = merge_jots(uow, &ids, MergeOriginals::Link)?;
+            Ok((merged, ids))
+        })
+        .unwrap();
+
+        assert_eq!(index::get_all_jots(&conn).unwrap().len(), 3);
+        for id in &ids {
+            assert!(merged.links.contains(id));
+        }
+    }
+
+    #[test]
+    fn test_split_inherits_metadata_and_trashes_original() {
+        let (vault, mut conn) = setup();
+        let created_at = Utc::now() - Duration::days(1);
+
+        let (original, parts) = unit_of_work::run(&mut conn, vault.path(), |uow| {
+            let original =
+                uow.create_jot_at("Buy milk\n\nCall Bob #todo", created_at, created_at)?;
+            let parts = split_jot(uow, &original.id, &[])?;
+            Ok((original, parts))
+        })
+        .unwrap();
+
+        let contents: Vec<&str> = parts.iter().map(|jot| jot.content.as_str()).collect();
+        assert_eq!(contents, vec!["Buy milk #todo", "Call Bob #todo"]);
+        assert!(parts.iter().all(|jot| jot.created_at == created_at));
+
+        assert!(index::get_jot(&conn, &original.id).unwrap().is_none());
+        assert!(!vault.path().join(&original.file_path).exists());
+    }
+}
@@ -0,0 +1,209 @@
+use crate::jots::frontmatter::FrontmatterSchema;
+use crate::jots::models::{ConsistencyReport, Jot, JotError};
+use crate::jots::{ids, index, storage};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Whether the index row still matches what's on disk
+fn index_matches_file(indexed: &Jot, file: &Jot) -> bool {
+    indexed.file_path == file.file_path
+        && indexed.content == file.content
+        && indexed.tags == file.tags
+        && indexed.links == file.links
+        && indexed.promoted == file.promoted
+        && indexed.archived == file.archived
+        && indexed.properties == file.properties
+        && indexed.created_at.timestamp_millis() == file.created_at.timestamp_millis()
+        && indexed.modified_at.timestamp_millis() == file.modified_at.timestamp_millis()
+}
+
+/// Compare .scribel/jots/ (archive subfolder included) against jot_index and report drift
+/// With `repair`, the index is brought back in line with the files (files are the source of truth)
+pub fn check_consistency(
+    conn: &mut Connection,
+    vault_path: &Path,
+    repair: bool,
+    schema: &FrontmatterSchema,
+) -> Result<ConsistencyReport, JotError> {
+    let mut report = ConsistencyReport::default();
+
+    let mut indexed: HashMap<String, Jot> = index::get_all_jots(conn)?
+        .into_iter()
+        .map(|jot| (jot.id.clone(), jot))
+        .collect();
+
+    // Jots that need to be (re)written to the index
+    let mut to_index = Vec::new();
+
+    for file_path in storage::list_jot_files(vault_path)? {
+        report.checked_files += 1;
+
+        let file_jot = match storage::read_jot_file(vault_path, &file_path, schema) {
+            Ok(jot) => jot,
+            Err(e) => {
+                eprintln!("Failed to read {}: {}", file_path, e);
+                // Keep its index row, if any: the file may be mid-edit
+                indexed.retain(|_, jot| jot.file_path != file_path);
+                report.unreadable.push(file_path);
+                continue;
+            }
+        };
+
+        match indexed.remove(&file_jot.id) {
+            None => {
+                report.missing_from_index.push(file_jot.id.clone());
+                to_index.push(file_jot);
+            }
+            Some(index_jot) if !index_matches_file(&index_jot, &file_jot) => {
+                report.out_of_date.push(file_jot.id.clone());
+                to_index.push(file_jot);
+            }
+            Some(_) => {}
+        }
+    }
+
+    // Whatever is left in the index has no file behind it
+    report.orphaned_in_index = indexed.into_keys().collect();
+
+    // Ids in creation order, whatever their format
+    report.missing_from_index.sort_by(|a, b| ids::compare(a, b));
+    report.out_of_date.sort_by(|a, b| ids::compare(a, b));
+    report.orphaned_in_index.sort_by(|a, b| ids::compare(a, b));
+    report.unreadable.sort();
+
+    if repair && report.has_drift() {
+        let tx = conn.transaction()?;
+        for jot in &to_index {
+            index::update_jot_index(&tx, jot)?;
+        }
+        for id in &report.orphaned_in_index {
+            index::delete_jot_index(&tx, id)?;
+        }
+        tx.commit()?;
+        report.repaired = true;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::storage::WriteOptions;
+    use crate::jots::test_support::setup;
+    use crate::jots::unit_of_work;
+    use std::fs;
+
+    #[test]
+    fn test_consistent_vault() {
+        let (vault, mut conn) = setup();
+        unit_of_work::run(&mut conn, vault.path(), |uow| uow.create_jot("In sync")).unwrap();
+
+        let report = check_consistency(
+            &mut conn,
+            vault.path(),
+            false,
+            &FrontmatterSchema::default(),
+        )
+        .unwrap();
+        assert_eq!(report.checked_files, 1);
+        assert!(!report.has_drift());
+    }
+
+    #[test]
+    fn test_detects_and_repairs_drift() {
+        let (vault, mut conn) = setup();
+
+        // File without index row
+        let unindexed =
+            storage::create_jot(vault.path(), "Never indexed", &WriteOptions::default()).unwrap();
+
+        // Index row without file
+        let orphan =
+            unit_of_work::run(&mut conn, vault.path(), |uow| uow.create_jot("Orphan")).unwrap();
+        fs::remove_file(vault.path().join(&orphan.file_path)).unwrap();
+
+        // File edited outside the app
+        let edited =
+            unit_of_work::run(&mut conn, vault.path(), |uow| uow.create_jot("Before")).unwrap();
+        storage::update_jot(
+            vault.path(),
+            edited.clone(),
+            "After",
+            &WriteOptions::default(),
+        )
+        .unwrap();
+
+        let report = check_consistency(
+            &mut conn,
+            vault.path(),
+            false,
+            &FrontmatterSchema::default(),
+        )
+        .unwrap();
+        assert_eq!(report.missing_from_index, vec![unindexed.id.clone()]);
+        assert_eq!(report.orphaned_in_index, vec![orphan.id.clone()]);
+        assert_eq!(report.out_of_date, vec![edited.id.clone()]);
+        assert!(!report.repaired);
+
+        let report =
+            check_consistency(&mut conn, vault.path(), true, &FrontmatterSchema::default())
+                .unwrap();
+        assert!(report.repaired);
+
+        let report = check_consistency(
+            &mut conn,
+            vault.path(),
+            false,
+            &FrontmatterSchema::default(),
+        )
+        .unwrap();
+        assert!(!report.has_drift());
+
+        let indexed = index::get_all_jots(&conn).unwrap();
+        assert_eq!(indexed.len(), 2);
+        assert!(indexed
+            .iter()
+            .any(|j| j.id == edited.id && j.content == "After"));
+    }
+
+    #[test]
+    fn test_repairs_frontmatter_properties_missing_from_index() {
+        let (vault, mut conn) = setup();
+        let jot = unit_of_work::run(&mut conn, vault.path(), |uow| uow.create_jot("Sync")).unwrap();
+        let path = vault.path().join(&jot.file_path);
+        let written = fs::read_to_string(&path).unwrap();
+        fs::write(&path, written.replacen("---\n", "---\nstatus: draft\n", 1)).unwrap();
+
+        // As after the properties migration: the index never saw the frontmatter key
+        let report =
+            check_consistency(&mut conn, vault.path(), true, &FrontmatterSchema::default())
+                .unwrap();
+        assert_eq!(report.out_of_date, vec![jot.id.clone()]);
+
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM jot_properties WHERE key = 'status' AND value = 'draft'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_unreadable_file_keeps_index_row() {
+        let (vault, mut conn) = setup();
+        let jot =
+            unit_of_work::run(&mut conn, vault.path(), |uow| uow.create_jot("Will break")).unwrap();
+        fs::write(vault.path().join(&jot.file_path), "not a jot").unwrap();
+
+        let report =
+            check_consistency(&mut conn, vault.path(), true, &FrontmatterSchema::default())
+                .unwrap();
+        assert_eq!(report.unreadable, vec![jot.file_path.clone()]);
+        assert!(report.orphaned_in_index.is_empty());
+        assert_eq!(index::get_all_jots(&conn).unwrap().len(), 1);
+    }
+}
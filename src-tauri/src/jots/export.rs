@@ -0,0 +1,272 @@
+use crate::jots::atomic;
+use crate::jots::models::{ExportFormat, ExportSummary, Jot, JotError};
+use crate::jots::storage::JOTS_DIR;
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Export jots to a single file at `destination` (absolute path)
+/// Never overwrites: an existing file at `destination` fails the export, like bulk export
+pub fn export_jots(
+    vault_path: &Path,
+    jots: &[Jot],
+    format: ExportFormat,
+    destination: &Path,
+) -> Result<ExportSummary, JotError> {
+    if !destination.is_absolute() {
+        return Err(JotError::validation(
+            "destination",
+            "Export destination must be an absolute path",
+        ));
+    }
+    if destination.exists() {
+        return Err(JotError::validation(
+            "destination",
+            format!("{} already exists", destination.display()),
+        ));
+    }
+
+    let bytes = match format {
+        ExportFormat::Markdown => to_markdown(jots).into_bytes(),
+        ExportFormat::Json => serde_json::to_vec_pretty(jots).map_err(std::io::Error::from)?,
+        ExportFormat::Csv => to_csv(jots)?,
+        ExportFormat::Html => to_html(jots).into_bytes(),
+        ExportFormat::Zip => to_zip(vault_path, jots)?,
+    };
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    atomic::write_atomic(destination, &bytes)?;
+
+    Ok(ExportSummary {
+        path: destination.to_string_lossy().to_string(),
+        format,
+        count: jots.len() as u32,
+    })
+}
+
+/// All jots in one Markdown file, one section per jot
+fn to_markdown(jots: &[Jot]) -> String {
+    let sections: Vec<String> = jots
+        .iter()
+        .map(|jot| {
+            format!(
+                "## {}\n\n{}\n",
+                jot.created_at.format("%Y-%m-%d %H:%M UTC"),
+                jot.content
+            )
+        })
+        .collect();
+
+    format!("# Jots\n\n{}", sections.join("\n---\n\n"))
+}
+
+/// One row per jot; tags and links are space-separated
+fn to_csv(jots: &[Jot]) -> Result<Vec<u8>, JotError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record([
+            "id",
+            "created_at",
+            "modified_at",
+            "tags",
+            "links",
+            "promoted",
+            "archived",
+            "content",
+        ])
+        .map_err(std::io::Error::from)?;
+
+    for jot in jots {
+        writer
+            .write_record([
+                jot.id.clone(),
+                jot.created_at.to_rfc3339(),
+                jot.modified_at.to_rfc3339(),
+                jot.tags.join(" "),
+                jot.links.join(" "),
+                jot.promoted.to_string(),
+                jot.archived.to_string(),
+                jot.content.clone(),
+            ])
+            .map_err(std::io::Error::from)?;
+    }
+
+    writer
+        .into_inner()
+        .map_err(|e| JotError::FileError(e.into_error()))
+}
+
+/// Escape text for HTML element content and attribute values
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Self-contained HTML page, one article per jot
+fn to_html(jots: &[Jot]) -> String {
+    let articles: Vec<String> = jots
+        .iter()
+        .map(|jot| {
+            let tags: String = jot
+                .tags
+                .iter()
+                .map(|tag| format!("<span class=\"tag\">#{}</span>", escape_html(tag)))
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!(
+                "<article id=\"{}\">\n<time datetime=\"{}\">{}</time>\n<p>{}</p>\n<footer>{}</footer>\n</article>",
+                escape_html(&jot.id),
+                jot.created_at.to_rfc3339(),
+                jot.created_at.format("%Y-%m-%d %H:%M UTC"),
+                escape_html(&jot.content),
+                tags
+            )
+        })
+        .collect();
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Jots</title>\n<style>\n\
+         body {{ font-family: sans-serif; max-width: 40em; margin: 2em auto; }}\n\
+         article {{ border-bottom: 1px solid #ddd; padding: 1em 0; }}\n\
+         time {{ color: #888; font-size: 0.9em; }}\n\
+         p {{ white-space: pre-wrap; }}\n\
+         .tag {{ color: #7c3aed; }}\n\
+         </style>\n</head>\n<body>\n<h1>Jots</h1>\n{}\n</body>\n</html>\n",
+        articles.join("\n")
+    )
+}
+
+/// Zip of the raw jot files, laid out as under .scribel/jots/
+fn to_zip(vault_path: &Path, jots: &[Jot]) -> Result<Vec<u8>, JotError> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for jot in jots {
+        let bytes = fs::read(vault_path.join(&jot.file_path))?;
+        let name = jot
+            .file_path
+            .strip_prefix(JOTS_DIR)
+            .map(|name| name.trim_start_matches('/'))
+            .unwrap_or(&jot.file_path);
+
+        zip.start_file(name, options)
+            .map_err(std::io::Error::from)?;
+        zip.write_all(&bytes)?;
+    }
+
+    let cursor = zip.finish().map_err(std::io::Error::from)?;
+    Ok(cursor.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::storage::{self, WriteOptions};
+    use std::io::Read;
+    use tempfile::tempdir;
+
+    fn create_jots(vault_path: &Path) -> Vec<Jot> {
+        vec![
+            storage::create_jot(vault_path, "First <idea> #work", &WriteOptions::default())
+                .unwrap(),
+            storage::create_jot(
+                vault_path,
+                "Second, with \"quotes\"\nand lines",
+                &WriteOptions::default(),
+            )
+            .unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_export_text_formats() {
+        let vault = tempdir().unwrap();
+        let jots = create_jots(vault.path());
+
+        let markdown = vault.path().join("out/jots.md");
+        export_jots(vault.path(), &jots, ExportFormat::Markdown, &markdown).unwrap();
+        let content = fs::read_to_string(&markdown).unwrap();
+        assert!(content.contains("First <idea> #work\n\n---\n\n## "));
+
+        let json = vault.path().join("out/jots.json");
+        let summary = export_jots(vault.path(), &jots, ExportFormat::Json, &json).unwrap();
+        assert_eq!(summary.count, 2);
+        let parsed: Vec<Jot> = serde_json::from_slice(&fs::read(&json).unwrap()).unwrap();
+        assert_eq!(parsed[1].content, jots[1].content);
+
+        let csv = vault.path().join("out/jots.csv");
+        export_jots(vault.path(), &jots, ExportFormat::Csv, &csv).unwrap();
+        let content = fs::read_to_string(&csv).unwrap();
+        assert!(
+            content.starts_with("id,created_at,modified_at,tags,links,promoted,archived,content\n")
+        );
+        assert!(content.contains("\"Second, with \"\"quotes\"\"\nand lines\""));
+
+        let html = vault.path().join("out/jots.html");
+        export_jots(vault.path(), &jots, ExportFormat::Html, &html).unwrap();
+        let content = fs::read_to_string(&html).unwrap();
+        assert!(content.contains("First &lt;idea&gt; #work"));
+        assert!(content.contains("<span class=\"tag\">#work</span>"));
+    }
+
+    #[test]
+    fn test_export_zip_keeps_raw_files() {
+        let vault = tempdir().unwrap();
+        let jots = create_jots(vault.path());
+
+        let destination = vault.path().join("jots.zip");
+        export_jots(vault.path(), &jots, ExportFormat::Zip, &destination).unwrap();
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&destination).unwrap()).unwrap();
+        assert_eq!(archive.len(), 2);
+
+        let mut raw = String::new();
+        archive
+            .by_name(&format!("{}.md", jots[0].id))
+            .unwrap()
+            .read_to_string(&mut raw)
+            .unwrap();
+        assert_eq!(
+            raw,
+            fs::read_to_string(vault.path().join(&jots[0].file_path)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_export_refuses_to_overwrite() {
+        let vault = tempdir().unwrap();
+        let jots = create_jots(vault.path());
+        let destination = vault.path().join("jots.json");
+        fs::write(&destination, "keep me").unwrap();
+
+        let result = export_jots(vault.path(), &jots, ExportFormat::Json, &destination);
+        assert!(matches!(result, Err(JotError::ValidationError { .. })));
+        assert_eq!(fs::read_to_string(&destination).unwrap(), "keep me");
+    }
+
+    #[test]
+    fn test_export_rejects_relative_destination() {
+        let vault = tempdir().unwrap();
+        let result = export_jots(
+            vault.path(),
+            &[],
+            ExportFormat::Json,
+            Path::new("jots.json"),
+        );
+        assert!(matches!(result, Err(JotError::ValidationError { .. })));
+    }
+}
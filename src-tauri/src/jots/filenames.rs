@@ -0,0 +1,183 @@
+use crate::jots::models::{Jot, JotError};
+use chrono::Local;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use std::path::Path;
+
+/// Default file name template: the jot id
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{{id}}.md";
+
+/// Words of content used by {{first-words}}
+const FIRST_WORDS: usize = 6;
+
+/// Longest file name (without extension) produced by a template, in characters
+const MAX_NAME_CHARS: usize = 100;
+
+static VARIABLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([a-z-]+)\s*\}\}").expect("Invalid template variable regex"));
+
+/// Characters Obsidian (or the file system) won't accept in file names, plus link syntax
+fn is_forbidden(c: char) -> bool {
+    matches!(
+        c,
+        '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']'
+    ) || c.is_control()
+}
+
+/// First words of the content, without markdown syntax or #tags
+fn first_words(content: &str) -> String {
+    content
+        .split_whitespace()
+        .filter(|word| !word.starts_with('#'))
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .take(FIRST_WORDS)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Make one path component safe: forbidden characters dropped, length capped,
+/// no leading or trailing dots and spaces (so never "." or "..")
+fn sanitize_component(component: &str) -> String {
+    let cleaned: String = component
+        .chars()
+        .filter(|&c| !is_forbidden(c))
+        .take(MAX_NAME_CHARS)
+        .collect();
+    cleaned
+        .trim_matches(|c: char| c == '.' || c.is_whitespace())
+        .to_string()
+}
+
+/// Path of a jot file under .scribel/jots/, rendered from a file name template
+///
+/// Variables: {{id}}, {{date}} (YYYY-MM-DD), {{time}} (HHMMSS), {{year}}, {{month}}, {{day}}
+/// and {{first-words}}; dates are the jot's creation time in local time. A "/" in the template
+/// makes subfolders (e.g. "{{year}}/{{month}}/{{date}} {{first-words}}.md").
+pub fn render(template: &str, jot: &Jot) -> Result<String, JotError> {
+    let created = jot.created_at.with_timezone(&Local);
+    let mut unknown: Option<String> = None;
+
+    let rendered = VARIABLE_REGEX.replace_all(template, |cap: &Captures| match &cap[1] {
+        "id" => jot.id.clone(),
+        "date" => created.format("%Y-%m-%d").to_string(),
+        "time" => created.format("%H%M%S").to_string(),
+        "year" => created.format("%Y").to_string(),
+        "month" => created.format("%m").to_string(),
+        "day" => created.format("%d").to_string(),
+        // A "/" in the content must not create folders
+        "first-words" => first_words(&jot.content).replace('/', " "),
+        other => {
+            unknown.get_or_insert_with(|| other.to_string());
+            String::new()
+        }
+    });
+    if let Some(variable) = unknown {
+        return Err(JotError::validation(
+            "filename_template",
+            format!("Unknown variable {{{{{}}}}}", variable),
+        ));
+    }
+
+    let rendered = rendered.strip_suffix(".md").unwrap_or(&rendered);
+    let mut components: Vec<String> = rendered
+        .split('/')
+        .map(sanitize_component)
+        .filter(|component| !component.is_empty())
+        .collect();
+
+    // The file name itself may not be lost (e.g. {{first-words}} of a jot that is only tags)
+    if rendered.ends_with('/') || components.is_empty() {
+        components.push(jot.id.clone());
+    }
+
+    Ok(format!("{}.md", components.join("/")))
+}
+
+/// `relative_path` (from the vault root), or "name 1.md", "name 2.md", ... if another file has it
+/// `own_path` is the jot's current file, which doesn't count as taken
+pub fn unique_path(vault_path: &Path, relative_path: &str, own_path: Option<&str>) -> String {
+    let stem = relative_path.strip_suffix(".md").unwrap_or(relative_path);
+
+    let mut candidate = relative_path.to_string();
+    let mut n = 1;
+    while Some(candidate.as_str()) != own_path && vault_path.join(&candidate).exists() {
+        candidate = format!("{} {}.md", stem, n);
+        n += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn jot(content: &str) -> Jot {
+        Jot {
+            id: "jot-01j9z3k8m2n4p6q8r0s2t4v6w8".to_string(),
+            content: content.to_string(),
+            created_at: Utc.with_ymd_and_hms(2025, 1, 19, 12, 0, 0).unwrap(),
+            modified_at: Utc.with_ymd_and_hms(2025, 1, 19, 12, 0, 0).unwrap(),
+            tags: vec![],
+            links: vec![],
+            promoted: false,
+            archived: false,
+            external_links: vec![],
+            properties: Default::default(),
+            file_path: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_templates() {
+        let jot = jot("#idea Call **Anna**: about the Q1/Q2 plan? [[Budget]] and more words");
+        let created = jot.created_at.with_timezone(&Local);
+
+        assert_eq!(
+            render(DEFAULT_FILENAME_TEMPLATE, &jot).unwrap(),
+            "jot-01j9z3k8m2n4p6q8r0s2t4v6w8.md"
+        );
+        assert_eq!(
+            render("{{year}}/{{month}}/{{date}} {{first-words}}", &jot).unwrap(),
+            format!(
+                "{}/{} Call Anna about the Q1 Q2 plan.md",
+                created.format("%Y/%m"),
+                created.format("%Y-%m-%d")
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_never_escapes_or_loses_the_name() {
+        let jot = jot("#only #tags");
+        assert_eq!(
+            render("../{{first-words}}.md", &jot).unwrap(),
+            "jot-01j9z3k8m2n4p6q8r0s2t4v6w8.md"
+        );
+        assert_eq!(
+            render("inbox/", &jot).unwrap(),
+            "inbox/jot-01j9z3k8m2n4p6q8r0s2t4v6w8.md"
+        );
+        assert!(matches!(
+            render("{{title}}.md", &jot),
+            Err(JotError::ValidationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unique_path() {
+        let vault = tempdir().unwrap();
+        fs::write(vault.path().join("note.md"), "").unwrap();
+        fs::write(vault.path().join("note 1.md"), "").unwrap();
+
+        assert_eq!(unique_path(vault.path(), "other.md", None), "other.md");
+        assert_eq!(unique_path(vault.path(), "note.md", None), "note 2.md");
+        assert_eq!(
+            unique_path(vault.path(), "note.md", Some("note.md")),
+            "note.md"
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use crate::jots::models::JotFrontmatter;
+use crate::jots::parser::{extract_links, extract_tags};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+
+/// Built-in frontmatter keys, in the order they are written
+const BUILTIN_KEYS: [&str; 8] = [
+    "id",
+    "created",
+    "modified",
+    "tags",
+    "links",
+    "promoted",
+    "archived",
+    "external_links",
+];
+
+/// Frontmatter key names of the built-in jot properties
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrontmatterKeys {
+    pub id: String,
+    pub created: String,
+    pub modified: String,
+    pub tags: String,
+    pub links: String,
+    pub promoted: String,
+    pub archived: String,
+    pub external_links: String,
+}
+
+impl Default for FrontmatterKeys {
+    fn default() -> Self {
+        Self {
+            id: "id".to_string(),
+            created: "created".to_string(),
+            modified: "modified".to_string(),
+            tags: "tags".to_string(),
+            links: "links".to_string(),
+            promoted: "promoted".to_string(),
+            archived: "archived".to_string(),
+            external_links: "external_links".to_string(),
+        }
+    }
+}
+
+impl FrontmatterKeys {
+    /// Configured key name of a built-in key
+    fn name_of<'a>(&'a self, builtin: &'a str) -> &'a str {
+        match builtin {
+            "id" => &self.id,
+            "created" => &self.created,
+            "modified" => &self.modified,
+            "tags" => &self.tags,
+            "links" => &self.links,
+            "promoted" => &self.promoted,
+            "archived" => &self.archived,
+            "external_links" => &self.external_links,
+            other => other,
+        }
+    }
+}
+
+/// How jot frontmatter is written and read (AppConfig `frontmatter`), e.g. for Dataview
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrontmatterSchema {
+    /// Key names of the built-in properties (e.g. "date" instead of "created")
+    pub keys: FrontmatterKeys,
+
+    /// Properties written into every jot as-is (e.g. type: jot); built-in keys take precedence
+    pub extra_properties: BTreeMap<String, Value>,
+
+    /// chrono format for created and modified, in local time (e.g. "%Y-%m-%d %H:%M")
+    /// RFC 3339 (UTC) if unset. Times are stored at the format's precision, so a coarser
+    /// format also makes edit conflict detection coarser.
+    pub date_format: Option<String>,
+
+    /// Write tags into the frontmatter (otherwise they are only read from the content)
+    pub include_tags: bool,
+
+    /// Write wiki-links into the frontmatter (otherwise they are only read from the content)
+    pub include_links: bool,
+}
+
+impl Default for FrontmatterSchema {
+    fn default() -> Self {
+        Self {
+            keys: FrontmatterKeys::default(),
+            extra_properties: BTreeMap::new(),
+            date_format: None,
+            include_tags: true,
+            include_links: true,
+        }
+    }
+}
+
+/// RFC 3339, as chrono serializes dates
+fn rfc3339(date: DateTime<Utc>) -> Value {
+    Value::String(date.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+}
+
+impl FrontmatterSchema {
+    /// Date as written to frontmatter
+    fn format_date(&self, date: DateTime<Utc>) -> Value {
+        match &self.date_format {
+            Some(format) => Value::String(date.with_timezone(&Local).format(format).to_string()),
+            None => rfc3339(date),
+        }
+    }
+
+    /// Date read from frontmatter: RFC 3339, or the configured format in local time
+    fn parse_date(&self, value: &Value) -> Option<DateTime<Utc>> {
+        let text = match value {
+            Value::String(text) => text.clone(),
+            // e.g. an unquoted 20250119 typed in Obsidian
+            Value::Number(number) => number.to_string(),
+            _ => return None,
+        };
+        if let Ok(date) = DateTime::parse_from_rfc3339(&text) {
+            return Some(date.with_timezone(&Utc));
+        }
+
+        let format = self.date_format.as_deref()?;
+        let naive = NaiveDateTime::parse_from_str(&text, format)
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(&text, format)
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })?;
+        Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|date| date.with_timezone(&Utc))
+    }
+
+    /// `date` as it reads back from a file written with this schema
+    pub fn stored_time(&self, date: DateTime<Utc>) -> DateTime<Utc> {
+        if self.date_format.is_none() {
+            return date;
+        }
+        self.parse_date(&self.format_date(date)).unwrap_or(date)
+    }
+
+    /// Frontmatter to write: configured key names and dates, then the jot's custom properties,
+    /// then the extra properties (neither overrides a key already written)
+    pub fn to_mapping(&self, frontmatter: &JotFrontmatter) -> Mapping {
+        let builtin = match serde_yaml::to_value(frontmatter) {
+            Ok(Value::Mapping(builtin)) => builtin,
+            _ => Mapping::new(),
+        };
+
+        let mut mapping = Mapping::new();
+        for (key, value) in builtin {
+            let Some(name) = key.as_str() else {
+                continue;
+            };
+            let value = match name {
+                "created" => self.format_date(frontmatter.created),
+                "modified" => self.format_date(frontmatter.modified),
+                "tags" if !self.include_tags => continue,
+                "links" if !self.include_links => continue,
+                _ => value,
+            };
+            mapping.insert(Value::from(self.keys.name_of(name)), value);
+        }
+
+        for (key, value) in frontmatter.properties.iter().chain(&self.extra_properties) {
+            let key = Value::from(key.as_str());
+            if !mapping.contains_key(&key) {
+                mapping.insert(key, value.clone());
+            }
+        }
+        mapping
+    }
+
+    /// Read frontmatter written with this schema or the default one
+    /// Tags and links missing from the frontmatter are taken from the content;
+    /// other keys become custom properties
+    pub fn parse_mapping(
+        &self,
+        mut mapping: Mapping,
+        content: &str,
+    ) -> Result<JotFrontmatter, serde_yaml::Error> {
+        let mut builtin = Mapping::new();
+        for name in BUILTIN_KEYS {
+            let value = mapping
+                .remove(self.keys.name_of(name))
+                .or_else(|| mapping.remove(name));
+            let Some(value) = value else {
+                continue;
+            };
+
+            let value = match name {
+                "created" | "modified" => self.parse_date(&value).map(rfc3339).unwrap_or(value),
+                _ => value,
+            };
+            builtin.insert(Value::from(name), value);
+        }
+
+        if !builtin.contains_key("tags") {
+            builtin.insert(Value::from("tags"), Value::from(extract_tags(content)));
+        }
+        if !builtin.contains_key("links") {
+            builtin.insert(Value::from("links"), Value::from(extract_links(content)));
+        }
+
+        let mut frontmatter: JotFrontmatter = serde_yaml::from_value(Value::Mapping(builtin))?;
+        frontmatter.properties = mapping
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.as_str()?.to_string(), value)))
+            .collect();
+        Ok(frontmatter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frontmatter() -> JotFrontmatter {
+        JotFrontmatter {
+            id: "jot-01j9z3k8m2n4p6q8r0s2t4v6w8".to_string(),
+            created: Utc.with_ymd_and_hms(2025, 1, 19, 14, 32, 56).unwrap(),
+            modified: Utc.with_ymd_and_hms(2025, 1, 20, 9, 0, 0).unwrap(),
+            tags: vec!["work".to_string()],
+            links: vec!["Project X".to_string()],
+            promoted: false,
+            archived: false,
+            external_links: vec![],
+            properties: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_default_schema_round_trip() {
+        let schema = FrontmatterSchema::default();
+        let mapping = schema.to_mapping(&frontmatter());
+
+        let keys: Vec<&str> = mapping.keys().filter_map(Value::as_str).collect();
+        assert_eq!(
+            keys,
+            vec!["id", "created", "modified", "tags", "links", "promoted", "archived"]
+        );
+        assert_eq!(
+            mapping.get("created"),
+            Some(&Value::from("2025-01-19T14:32:56Z"))
+        );
+
+        let parsed = schema.parse_mapping(mapping, "").unwrap();
+        assert_eq!(parsed.created, frontmatter().created);
+        assert_eq!(parsed.tags, vec!["work"]);
+    }
+
+    #[test]
+    fn test_custom_schema_round_trip() {
+        let schema = FrontmatterSchema {
+            keys: FrontmatterKeys {
+                created: "date".to_string(),
+                ..FrontmatterKeys::default()
+            },
+            extra_properties: BTreeMap::from([
+                ("type".to_string(), Value::from("jot")),
+                ("id".to_string(), Value::from("never written")),
+            ]),
+            date_format: Some("%Y-%m-%d %H:%M".to_string()),
+            include_tags: false,
+            include_links: false,
+        };
+        let original = frontmatter();
+        let mapping = schema.to_mapping(&original);
+
+        assert!(mapping.get("created").is_none());
+        assert!(mapping.get("tags").is_none());
+        assert_eq!(mapping.get("type"), Some(&Value::from("jot")));
+        assert_eq!(mapping.get("id"), Some(&Value::from(original.id.as_str())));
+        let local = original.created.with_timezone(&Local);
+        assert_eq!(
+            mapping.get("date"),
+            Some(&Value::from(local.format("%Y-%m-%d %H:%M").to_string()))
+        );
+
+        let parsed = schema
+            .parse_mapping(mapping, "Call about [[Budget]] #work")
+            .unwrap();
+        assert_eq!(parsed.created, schema.stored_time(original.created));
+        assert_eq!(parsed.created.timestamp() % 60, 0);
+        assert_eq!(parsed.tags, vec!["work"]);
+        assert_eq!(parsed.links, vec!["Budget"]);
+    }
+
+    #[test]
+    fn test_custom_properties_round_trip() {
+        let schema = FrontmatterSchema {
+            extra_properties: BTreeMap::from([("type".to_string(), Value::from("jot"))]),
+            ..FrontmatterSchema::default()
+        };
+        let mut original = frontmatter();
+        original.properties = BTreeMap::from([
+            ("mood".to_string(), Value::from("calm")),
+            ("type".to_string(), Value::from("meeting")),
+            ("id".to_string(), Value::from("never written")),
+        ]);
+        let mapping = schema.to_mapping(&original);
+
+        assert_eq!(mapping.get("id"), Some(&Value::from(original.id.as_str())));
+        assert_eq!(mapping.get("type"), Some(&Value::from("meeting")));
+
+        let parsed = schema.parse_mapping(mapping, "").unwrap();
+        assert_eq!(parsed.id, original.id);
+        let keys: Vec<&str> = parsed.properties.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["mood", "type"]);
+    }
+
+    #[test]
+    fn test_custom_schema_reads_default_keys() {
+        // Files written before the schema was configured
+        let schema = FrontmatterSchema {
+            keys: FrontmatterKeys {
+                created: "date".to_string(),
+                ..FrontmatterKeys::default()
+            },
+            date_format: Some("%Y-%m-%d".to_string()),
+            ..FrontmatterSchema::default()
+        };
+        let mapping = FrontmatterSchema::default().to_mapping(&frontmatter());
+
+        let parsed = schema.parse_mapping(mapping, "").unwrap();
+        assert_eq!(parsed.created, frontmatter().created);
+
+        let mut typed: Mapping = serde_yaml::from_str(
+            "id: jot-a\ndate: 2025-01-19\nmodified: 2025-01-20\npromoted: false",
+        )
+        .unwrap();
+        typed.insert(Value::from("tags"), Value::from(vec!["x"]));
+        let parsed = schema.parse_mapping(typed, "").unwrap();
+        let expected = Local.with_ymd_and_hms(2025, 1, 19, 0, 0, 0).unwrap();
+        assert_eq!(parsed.created, expected.with_timezone(&Utc));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::jots::models::{DiffKind, DiffLine, Jot, JotError, JotRevision};
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, Result as SqlResult, Row};
+
+/// Map a jot_revisions row to JotRevision
+fn row_to_revision(row: &Row) -> SqlResult<JotRevision> {
+    let tags_json: String = row.get(3)?;
+    let links_json: String = row.get(4)?;
+    let promoted: i32 = row.get(5)?;
+    let created_at_ms: i64 = row.get(6)?;
+    let modified_at_ms: i64 = row.get(7)?;
+    let recorded_at_ms: i64 = row.get(8)?;
+
+    Ok(JotRevision {
+        revision_id: row.get(0)?,
+        jot_id: row.get(1)?,
+        content: row.get(2)?,
+        tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+        links: serde_json::from_str(&links_json).unwrap_or_default(),
+        promoted: promoted != 0,
+        created_at: chrono::DateTime::from_timestamp_millis(created_at_ms).unwrap_or_default(),
+        modified_at: chrono::DateTime::from_timestamp_millis(modified_at_ms).unwrap_or_default(),
+        recorded_at: chrono::DateTime::from_timestamp_millis(recorded_at_ms).unwrap_or_default(),
+    })
+}
+
+/// Record the state of a jot before it gets modified
+/// Returns the new revision id
+pub fn record_revision(conn: &Connection, jot: &Jot) -> Result<i64, JotError> {
+    let tags_json = serde_json::to_string(&jot.tags).unwrap_or_else(|_| "[]".to_string());
+    let links_json = serde_json::to_string(&jot.links).unwrap_or_else(|_| "[]".to_string());
+
+    conn.execute(
+        "INSERT INTO jot_revisions (jot_id, content, tags, links, promoted, created_at, modified_at, recorded_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        rusqlite::params![
+            jot.id,
+            jot.content,
+            tags_json,
+            links_json,
+            if jot.promoted { 1 } else { 0 },
+            jot.created_at.timestamp_millis(),
+            jot.modified_at.timestamp_millis(),
+            Utc::now().timestamp_millis(),
+        ],
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+/// List revisions of a jot (newest first)
+pub fn list_revisions(conn: &Connection, jot_id: &str) -> Result<Vec<JotRevision>, JotError> {
+    let mut stmt = conn.prepare(
+        "SELECT revision_id, jot_id, content, tags, links, promoted, created_at, modified_at, recorded_at
+         FROM jot_revisions
+         WHERE jot_id = ?1
+         ORDER BY revision_id DESC",
+    )?;
+
+    let revisions = stmt
+        .query_map(rusqlite::params![jot_id], row_to_revision)?
+        .collect::<SqlResult<Vec<JotRevision>>>()?;
+
+    Ok(revisions)
+}
+
+/// Get a single revision by its id
+pub fn get_revision(conn: &Connection, revision_id: i64) -> Result<JotRevision, JotError> {
+    conn.query_row(
+        "SELECT revision_id, jot_id, content, tags, links, promoted, created_at, modified_at, recorded_at
+         FROM jot_revisions
+         WHERE revision_id = ?1",
+        rusqlite::params![revision_id],
+        row_to_revision,
+    )
+    .optional()?
+    .ok_or_else(|| JotError::NotFound(format!("revision {}", revision_id)))
+}
+
+/// Pairs of (index in `a`, index in `b`) for lines in their longest common subsequence
+pub fn matching_lines(a: &[&str], b: &[&str]) -> Vec<(usize, usize)> {
+    // lcs[i][j] = length of the longest common subsequence of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut matches = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            matches.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    matches
+}
+
+/// Line-based diff from `old` to `new` (longest common subsequence)
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    let line = |kind, text: &str| DiffLine {
+        kind,
+        text: text.to_string(),
+    };
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for (mi, mj) in matching_lines(&a, &b) {
+        diff.extend(a[i..mi].iter().map(|text| line(DiffKind::Delete, text)));
+        diff.extend(b[j..mj].iter().map(|text| line(DiffKind::Insert, text)));
+        diff.push(line(DiffKind::Equal, a[mi]));
+        i = mi + 1;
+        j = mj + 1;
+    }
+    diff.extend(a[i..].iter().map(|text| line(DiffKind::Delete, text)));
+    diff.extend(b[j..].iter().map(|text| line(DiffKind::Insert, text)));
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_jot(id: &str, content: &str) -> Jot {
+        let now = Utc::now();
+        Jot {
+            id: id.to_string(),
+            content: content.to_string(),
+            created_at: now,
+            modified_at: now,
+            tags: vec!["test".to_string()],
+            links: vec![],
+            promoted: false,
+            archived: false,
+            external_links: vec![],
+            properties: Default::default(),
+            file_path: format!(".scribel/jots/{}.md", id),
+        }
+    }
+
+    #[test]
+    fn test_record_and_list_revisions() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        let mut jot = create_test_jot("jot-1", "First draft");
+        record_revision(&conn, &jot).unwrap();
+
+        jot.content = "Second draft".to_string();
+        record_revision(&conn, &jot).unwrap();
+
+        record_revision(&conn, &create_test_jot("jot-2", "Other jot")).unwrap();
+
+        let revisions = list_revisions(&conn, "jot-1").unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].content, "Second draft");
+        assert_eq!(revisions[1].content, "First draft");
+        assert_eq!(revisions[1].tags, vec!["test"]);
+    }
+
+    #[test]
+    fn test_get_revision() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        let revision_id = record_revision(&conn, &create_test_jot("jot-1", "Draft")).unwrap();
+
+        let revision = get_revision(&conn, revision_id).unwrap();
+        assert_eq!(revision.jot_id, "jot-1");
+        assert_eq!(revision.content, "Draft");
+
+        let result = get_revision(&conn, revision_id + 1);
+        assert!(matches!(result, Err(JotError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("keep\nold line\ntail", "keep\nnew line\ntail\nextra");
+
+        let kinds: Vec<DiffKind> = diff.iter().map(|l| l.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                DiffKind::Equal,
+                DiffKind::Delete,
+                DiffKind::Insert,
+                DiffKind::Equal,
+                DiffKind::Insert,
+            ]
+        );
+        assert_eq!(diff[1].text, "old line");
+        assert_eq!(diff[2].text, "new line");
+        assert_eq!(diff[4].text, "extra");
+    }
+
+    #[test]
+    fn test_diff_lines_identical() {
+        let diff = diff_lines("same\ntext", "same\ntext");
+        assert!(diff.iter().all(|l| l.kind == DiffKind::Equal));
+        assert_eq!(diff.len(), 2);
+    }
+}
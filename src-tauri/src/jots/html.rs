@@ -0,0 +1,74 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+static BLOCK_END_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)<br\s*/?>|</(p|div|h[1-6]|li|tr|blockquote)>").expect("Invalid block regex")
+});
+
+static HIDDEN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?is)<(head|style|script|noscript)[^>]*>.*?</(head|style|script|noscript)>")
+        .expect("Invalid hidden element regex")
+});
+
+static LIST_ITEM_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)<li[^>]*>").expect("Invalid list item regex"));
+
+static TAG_MARKUP_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<[^>]+>").expect("Invalid markup regex"));
+
+static ENTITY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"&(#x?[0-9a-fA-F]+|[a-zA-Z]+);").expect("Invalid entity regex"));
+
+static BLANK_LINES_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\n[ \t]*(\n[ \t]*)+").expect("Invalid blank lines regex"));
+
+/// Decode HTML character references (&amp;, &#33;, &#x21;, ...); unknown ones are kept as-is
+pub fn decode_entities(text: &str) -> String {
+    ENTITY_REGEX
+        .replace_all(text, |cap: &Captures| {
+            let entity = &cap[1];
+            let decoded = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| cap[0].to_string(), String::from)
+        })
+        .into_owned()
+}
+
+/// Plain text of an HTML document: block ends become line breaks, list items become "- "
+pub fn to_text(html: &str) -> String {
+    let text = HIDDEN_REGEX.replace_all(html, "");
+    let text = BLOCK_END_REGEX.replace_all(&text, "\n");
+    let text = LIST_ITEM_REGEX.replace_all(&text, "- ");
+    let text = TAG_MARKUP_REGEX.replace_all(&text, "");
+    let text = decode_entities(&text);
+    let text = BLANK_LINES_REGEX.replace_all(&text, "\n\n");
+
+    text.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_text() {
+        let html = "<html><head><title>x</title></head><body><div>Groceries &amp; more</div>\
+                    <ul><li>Milk</li><li>Eggs</li></ul><p>Done&#33;<br>Bye</p></body></html>";
+        assert_eq!(
+            to_text(html),
+            "Groceries & more\n- Milk\n- Eggs\nDone!\nBye"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rand::Rng;
+use std::cmp::Ordering;
+use std::sync::Mutex;
+
+/// Prefix shared by every jot id
+const PREFIX: &str = "jot-";
+
+/// Crockford base32, lowercase (ids are also file names)
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+/// Random part of a ULID: 80 bits
+const RANDOM_MASK: u128 = (1 << 80) - 1;
+
+/// Last id handed out in this process: (milliseconds, random part)
+static LAST: Mutex<(u64, u128)> = Mutex::new((0, 0));
+
+/// Next (milliseconds, random) pair, strictly greater than the last one
+/// Within one millisecond (or if the clock goes back) the random part is incremented,
+/// so ids created in a burst stay unique and in creation order
+fn next_parts(now_ms: u64) -> (u64, u128) {
+    let mut last = LAST.lock().unwrap_or_else(|e| e.into_inner());
+    let next = if now_ms > last.0 {
+        (now_ms, rand::thread_rng().gen::<u128>() & RANDOM_MASK)
+    } else if last.1 < RANDOM_MASK {
+        (last.0, last.1 + 1)
+    } else {
+        (last.0 + 1, 0)
+    };
+    *last = next;
+    next
+}
+
+/// Encode 48-bit milliseconds + 80 random bits as 26 base32 characters
+fn encode(ms: u64, random: u128) -> String {
+    let value = (u128::from(ms) << 80) | random;
+    (0..26)
+        .rev()
+        .map(|i| ALPHABET[((value >> (i * 5)) & 0x1f) as usize] as char)
+        .collect()
+}
+
+/// Generate a new jot id: "jot-" followed by a lowercase ULID
+/// Ids sort by creation time and never repeat within the process
+pub fn generate() -> String {
+    let now_ms = Utc::now().timestamp_millis().max(0) as u64;
+    let (ms, random) = next_parts(now_ms);
+    format!("{}{}", PREFIX, encode(ms, random))
+}
+
+/// Creation time encoded in a jot id, for ULID ids and legacy "jot-YYYY-MM-DD-HHMMSS-XXXX" ids
+pub fn timestamp(id: &str) -> Option<DateTime<Utc>> {
+    let rest = id.strip_prefix(PREFIX)?;
+
+    if rest.len() == 26 {
+        let mut value: u128 = 0;
+        for c in rest.chars() {
+            let digit = ALPHABET
+                .iter()
+                .position(|&a| a as char == c.to_ascii_lowercase())?;
+            value = (value << 5) | digit as u128;
+        }
+        return DateTime::from_timestamp_millis((value >> 80) as i64);
+    }
+
+    // Legacy: second resolution, then four hex digits
+    let (time, suffix) = rest.rsplit_once('-')?;
+    if suffix.is_empty() || !suffix.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    NaiveDateTime::parse_from_str(time, "%Y-%m-%d-%H%M%S")
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+/// Order jot ids by creation time, across both formats; ties (and unparseable ids) by the id
+pub fn compare(a: &str, b: &str) -> Ordering {
+    timestamp(a).cmp(&timestamp(b)).then_with(|| a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_generated_ids_are_unique_and_ordered() {
+        let ids: Vec<String> = (0..10_000).map(|_| generate()).collect();
+
+        let unique: HashSet<&String> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len());
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids
+            .iter()
+            .all(|id| id.len() == 30 && timestamp(id).is_some()));
+    }
+
+    #[test]
+    fn test_random_part_overflow_moves_to_next_millisecond() {
+        assert!(encode(5, RANDOM_MASK) < encode(6, 0));
+        assert_eq!(encode(0, 0), "0".repeat(26));
+        assert_eq!(
+            encode((1 << 48) - 1, RANDOM_MASK),
+            format!("7{}", "z".repeat(25))
+        );
+    }
+
+    #[test]
+    fn test_timestamp_of_both_formats() {
+        let before = Utc::now().timestamp_millis();
+        let id = generate();
+        let ms = timestamp(&id).unwrap().timestamp_millis();
+        assert!(ms >= before && ms <= Utc::now().timestamp_millis());
+
+        let legacy = timestamp("jot-2025-01-19-143256-a1b2").unwrap();
+        assert_eq!(legacy.to_rfc3339(), "2025-01-19T14:32:56+00:00");
+
+        assert_eq!(timestamp("jot-2025-01-19-143256"), None);
+        assert_eq!(timestamp("note-2025-01-19-143256-a1b2"), None);
+        assert_eq!(timestamp("jot-not-an-id"), None);
+    }
+
+    #[test]
+    fn test_compare_mixes_formats_by_time() {
+        let legacy_old = "jot-2020-05-01-090000-ffff";
+        let legacy_new = "jot-2099-05-01-090000-0000";
+        let current = generate();
+
+        let mut ids = vec![legacy_new, current.as_str(), legacy_old];
+        ids.sort_by(|a, b| compare(a, b));
+        assert_eq!(ids, vec![legacy_old, current.as_str(), legacy_new]);
+    }
+}
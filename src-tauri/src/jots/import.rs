@@ -0,0 +1,580 @@
+use crate::jots::models::{ImportFailure, ImportReport, ImportSource, JotError};
+use crate::jots::unit_of_work::UnitOfWork;
+use crate::jots::{html, parser, storage};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Note read from another tool, before it becomes a jot
+struct ImportedNote {
+    /// File (and entry) it came from, for the report
+    source: String,
+    content: String,
+    created_at: DateTime<Utc>,
+    modified_at: DateTime<Utc>,
+    tags: Vec<String>,
+}
+
+/// Notes read from an export, plus what could not be read
+#[derive(Default)]
+struct Collected {
+    notes: Vec<ImportedNote>,
+    skipped: u32,
+    failed: Vec<ImportFailure>,
+}
+
+impl Collected {
+    fn fail(&mut self, source: String, message: impl ToString) {
+        self.failed.push(ImportFailure {
+            source,
+            message: message.to_string(),
+        });
+    }
+}
+
+/// Hash used to detect notes already present as jots (whitespace at the ends ignored)
+pub fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.trim().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Files with one of `extensions` at `path` (a folder, searched recursively, or a single file)
+fn source_files(path: &Path, extensions: &[&str]) -> Result<Vec<PathBuf>, JotError> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    if !path.is_dir() {
+        return Err(JotError::validation(
+            "path",
+            format!("{} does not exist", path.display()),
+        ));
+    }
+
+    let mut files = Vec::new();
+    storage::collect_files(path, &mut files)?;
+    files.retain(|file| {
+        file.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.contains(&ext.to_lowercase().as_str()))
+    });
+    files.sort();
+    Ok(files)
+}
+
+/// Creation and modification times of a file (creation falls back to modification)
+fn file_times(path: &Path) -> Result<(DateTime<Utc>, DateTime<Utc>), JotError> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata.modified()?;
+    let created = metadata.created().unwrap_or(modified);
+    Ok((created.into(), modified.into()))
+}
+
+/// Tool labels as jot tags: whitespace becomes '-', names that can't be tags are dropped
+fn to_tags(labels: impl IntoIterator<Item = String>) -> Vec<String> {
+    labels
+        .into_iter()
+        .filter_map(|label| {
+            let label = label.split_whitespace().collect::<Vec<_>>().join("-");
+            parser::normalize_tag(&label).ok()
+        })
+        .collect()
+}
+
+/// Apple Notes: one HTML file per note, timestamps from the file
+fn read_apple_notes(path: &Path, collected: &mut Collected) -> Result<(), JotError> {
+    for file in source_files(path, &["html", "htm"])? {
+        let source = file.display().to_string();
+        let read = fs::read_to_string(&file)
+            .map_err(JotError::from)
+            .and_then(|html| {
+                let (created_at, modified_at) = file_times(&file)?;
+                Ok((html::to_text(&html), created_at, modified_at))
+            });
+        match read {
+            Ok((content, created_at, modified_at)) => collected.notes.push(ImportedNote {
+                source,
+                content,
+                created_at,
+                modified_at,
+                tags: vec![],
+            }),
+            Err(e) => collected.fail(source, e),
+        }
+    }
+    Ok(())
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct KeepNote {
+    title: String,
+    text_content: String,
+    list_content: Vec<KeepListItem>,
+    labels: Vec<KeepLabel>,
+    created_timestamp_usec: i64,
+    user_edited_timestamp_usec: i64,
+    is_trashed: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct KeepListItem {
+    text: String,
+    is_checked: bool,
+}
+
+#[derive(Deserialize)]
+struct KeepLabel {
+    name: String,
+}
+
+/// Google Keep Takeout: one JSON file per note; titles become the first line, lists become checkboxes
+fn read_google_keep(path: &Path, collected: &mut Collected) -> Result<(), JotError> {
+    for file in source_files(path, &["json"])? {
+        let source = file.display().to_string();
+        let note: KeepNote = match read_json(&file) {
+            Ok(note) => note,
+            Err(e) => {
+                collected.fail(source, e);
+                continue;
+            }
+        };
+
+        if note.is_trashed {
+            collected.skipped += 1;
+            continue;
+        }
+
+        let body = if note.list_content.is_empty() {
+            note.text_content
+        } else {
+            note.list_content
+                .iter()
+                .map(|item| {
+                    format!(
+                        "- [{}] {}",
+                        if item.is_checked { "x" } else { " " },
+                        item.text
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let content = [note.title.trim(), body.trim()]
+            .iter()
+            .filter(|part| !part.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        // Notes without timestamps take the export file's times instead of 1970
+        let (file_created, file_modified) = match file_times(&file) {
+            Ok(times) => times,
+            Err(e) => {
+                collected.fail(source, e);
+                continue;
+            }
+        };
+        let created_at = DateTime::from_timestamp_micros(note.created_timestamp_usec)
+            .filter(|_| note.created_timestamp_usec > 0)
+            .unwrap_or(file_created);
+        let modified_at = DateTime::from_timestamp_micros(note.user_edited_timestamp_usec)
+            .filter(|_| note.user_edited_timestamp_usec > 0)
+            .unwrap_or(if note.created_timestamp_usec > 0 {
+                created_at
+            } else {
+                file_modified
+            });
+
+        collected.notes.push(ImportedNote {
+            source,
+            content,
+            created_at,
+            modified_at,
+            tags: to_tags(note.labels.into_iter().map(|label| label.name)),
+        });
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct DraftsEntry {
+    content: String,
+    created_at: DateTime<Utc>,
+    #[serde(default)]
+    modified_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DayOneEntry {
+    #[serde(default)]
+    text: String,
+    creation_date: DateTime<Utc>,
+    #[serde(default)]
+    modified_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct DayOneExport {
+    entries: Vec<serde_json::Value>,
+}
+
+/// Read a JSON export file holding a list of entries; bad entries are reported one by one
+fn read_entries<T, F>(
+    file: &Path,
+    entries: Vec<serde_json::Value>,
+    collected: &mut Collected,
+    to_note: F,
+) where
+    T: for<'de> Deserialize<'de>,
+    F: Fn(String, T) -> ImportedNote,
+{
+    for (i, entry) in entries.into_iter().enumerate() {
+        let source = format!("{}#{}", file.display(), i + 1);
+        match serde_json::from_value::<T>(entry) {
+            Ok(entry) => collected.notes.push(to_note(source, entry)),
+            Err(e) => collected.fail(source, e),
+        }
+    }
+}
+
+/// Read and parse a whole JSON export file
+fn read_json<T: for<'de> Deserialize<'de>>(file: &Path) -> Result<T, JotError> {
+    let json = fs::read_to_string(file)?;
+    serde_json::from_str(&json).map_err(|e| JotError::ParseError {
+        file: file.display().to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// Drafts: a JSON array of drafts with ISO 8601 timestamps
+fn read_drafts(path: &Path, collected: &mut Collected) -> Result<(), JotError> {
+    let entries: Vec<serde_json::Value> = read_json(path)?;
+    read_entries(path, entries, collected, |source, draft: DraftsEntry| {
+        ImportedNote {
+            source,
+            content: draft.content,
+            created_at: draft.created_at,
+            modified_at: draft.modified_at.unwrap_or(draft.created_at),
+            tags: to_tags(draft.tags),
+        }
+    });
+    Ok(())
+}
+
+/// Day One: a JSON object with an `entries` array
+fn read_day_one(path: &Path, collected: &mut Collected) -> Result<(), JotError> {
+    let export: DayOneExport = read_json(path)?;
+    read_entries(
+        path,
+        export.entries,
+        collected,
+        |source, entry: DayOneEntry| ImportedNote {
+            source,
+            content: entry.text,
+            created_at: entry.creation_date,
+            modified_at: entry.modified_date.unwrap_or(entry.creation_date),
+            tags: to_tags(entry.tags),
+        },
+    );
+    Ok(())
+}
+
+/// Split optional YAML frontmatter off a Markdown file, returning its tags and the body
+fn split_frontmatter(text: &str) -> (Vec<String>, &str) {
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return (vec![], text);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (vec![], text);
+    };
+
+    let tags = match serde_yaml::from_str::<serde_yaml::Value>(&rest[..end])
+        .ok()
+        .and_then(|yaml| yaml.get("tags").cloned())
+    {
+        Some(serde_yaml::Value::Sequence(tags)) => tags
+            .iter()
+            .filter_map(|tag| tag.as_str().map(str::to_string))
+            .collect(),
+        Some(serde_yaml::Value::String(tags)) => tags
+            .split([',', ' '])
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect(),
+        _ => vec![],
+    };
+
+    let body = rest[end + 4..].trim_start_matches(['-', '\r', '\n']);
+    (tags, body)
+}
+
+/// Plain text / Markdown folder: one note per file, timestamps from the file
+/// Frontmatter is dropped; its tags are kept
+fn read_markdown(path: &Path, collected: &mut Collected) -> Result<(), JotError> {
+    for file in source_files(path, &["md", "markdown", "txt"])? {
+        let source = file.display().to_string();
+        let read = fs::read_to_string(&file)
+            .map_err(JotError::from)
+            .and_then(|text| {
+                let (created_at, modified_at) = file_times(&file)?;
+                Ok((text, created_at, modified_at))
+            });
+        match read {
+            Ok((text, created_at, modified_at)) => {
+                let (tags, body) = split_frontmatter(&text);
+                collected.notes.push(ImportedNote {
+                    source,
+                    content: body.to_string(),
+                    created_at,
+                    modified_at,
+                    tags: to_tags(tags),
+                });
+            }
+            Err(e) => collected.fail(source, e),
+        }
+    }
+    Ok(())
+}
+
+/// Import notes exported from another tool as jots
+/// `known_hashes` are content hashes of existing jots; matching notes are counted as duplicates.
+/// Each note is created on its own: a failing note is reported and the rest still import.
+pub fn import_jots(
+    uow: &mut UnitOfWork,
+    source: ImportSource,
+    path: &Path,
+    mut known_hashes: HashSet<u64>,
+) -> Result<ImportReport, JotError> {
+    let mut collected = Collected::default();
+    match source {
+        ImportSource::AppleNotes => read_apple_notes(path, &mut collected)?,
+        ImportSource::GoogleKeep => read_google_keep(path, &mut collected)?,
+        ImportSource::Drafts => read_drafts(path, &mut collected)?,
+        ImportSource::Markdown => read_markdown(path, &mut collected)?,
+        ImportSource::DayOne => read_day_one(path, &mut collected)?,
+    }
+
+    let mut report = ImportReport {
+        skipped: collected.skipped,
+        failed: collected.failed,
+        ..Default::default()
+    };
+
+    for note in collected.notes {
+        if note.content.trim().is_empty() {
+            report.skipped += 1;
+            continue;
+        }
+
+        // Tags from the other tool live in the content, like any jot tag
+        let content = note
+            .tags
+            .iter()
+            .fold(note.content.trim().to_string(), |content, tag| {
+                parser::add_tag(&content, tag)
+            });
+
+        // Only notes that made it in count against later duplicates
+        let hash = content_hash(&content);
+        if known_hashes.contains(&hash) {
+            report.duplicates += 1;
+            continue;
+        }
+
+        match uow.isolated(|uow| uow.create_jot_at(&content, note.created_at, note.modified_at)) {
+            Ok(jot) => {
+                known_hashes.insert(hash);
+                report.imported.push(jot.id);
+            }
+            Err(e) => report.failed.push(ImportFailure {
+                source: note.source,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::storage::WriteOptions;
+    use crate::jots::test_support;
+    use crate::jots::validation::ContentRules;
+    use crate::jots::{index, unit_of_work};
+    use rusqlite::Connection;
+    use tempfile::tempdir;
+
+    fn run_import(
+        vault_path: &Path,
+        conn: &mut Connection,
+        source: ImportSource,
+        path: &Path,
+    ) -> ImportReport {
+        let known = index::get_all_jots(conn)
+            .unwrap()
+            .iter()
+            .map(|jot| content_hash(&jot.content))
+            .collect();
+        unit_of_work::run(conn, vault_path, |uow| {
+            import_jots(uow, source, path, known)
+        })
+        .unwrap()
+    }
+
+    fn setup() -> (tempfile::TempDir, tempfile::TempDir, Connection) {
+        let (vault, conn) = test_support::setup();
+        (vault, tempdir().unwrap(), conn)
+    }
+
+    #[test]
+    fn test_import_google_keep_preserves_timestamps_and_labels() {
+        let (vault, export, mut conn) = setup();
+        fs::write(
+            export.path().join("note.json"),
+            r#"{"title": "Shopping", "listContent": [{"text": "Milk", "isChecked": true}],
+                "labels": [{"name": "Home Stuff"}], "createdTimestampUsec": 1600000000000000,
+                "userEditedTimestampUsec": 1600000500000000, "isTrashed": false}"#,
+        )
+        .unwrap();
+        fs::write(
+            export.path().join("trashed.json"),
+            r#"{"textContent": "Old", "createdTimestampUsec": 1600000000000000, "isTrashed": true}"#,
+        )
+        .unwrap();
+        let undated = export.path().join("undated.json");
+        fs::write(&undated, r#"{"textContent": "Undated"}"#).unwrap();
+
+        let report = run_import(
+            vault.path(),
+            &mut conn,
+            ImportSource::GoogleKeep,
+            export.path(),
+        );
+        assert_eq!(report.imported.len(), 2);
+        assert_eq!(report.skipped, 1);
+
+        let jot = index::get_jot(&conn, &report.imported[0]).unwrap().unwrap();
+        assert_eq!(jot.content, "Shopping\n\n- [x] Milk #Home-Stuff");
+        assert_eq!(jot.tags, vec!["Home-Stuff"]);
+        assert_eq!(jot.created_at.timestamp(), 1_600_000_000);
+        assert_eq!(jot.modified_at.timestamp(), 1_600_000_500);
+
+        // Without timestamps the note keeps the export file's times
+        let jot = index::get_jot(&conn, &report.imported[1]).unwrap().unwrap();
+        let (created, modified) = file_times(&undated).unwrap();
+        assert_eq!(jot.content, "Undated");
+        assert_eq!(jot.created_at.timestamp(), created.timestamp());
+        assert_eq!(jot.modified_at.timestamp(), modified.timestamp());
+    }
+
+    #[test]
+    fn test_import_drafts_dedupes_and_reports_bad_entries() {
+        let (vault, export, mut conn) = setup();
+        let file = export.path().join("drafts.json");
+        fs::write(
+            &file,
+            r#"[
+                {"content": "Call Bob", "created_at": "2021-03-04T05:06:07Z", "tags": ["work"]},
+                {"content": "Call Bob", "created_at": "2021-03-05T05:06:07Z", "tags": ["work"]},
+                {"content": "No date"}
+            ]"#,
+        )
+        .unwrap();
+
+        let report = run_import(vault.path(), &mut conn, ImportSource::Drafts, &file);
+        assert_eq!(report.imported.len(), 1);
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(report.failed.len(), 1);
+        assert!(report.failed[0].source.ends_with("drafts.json#3"));
+
+        // Importing again finds everything already there
+        let report = run_import(vault.path(), &mut conn, ImportSource::Drafts, &file);
+        assert!(report.imported.is_empty());
+        assert_eq!(report.duplicates, 2);
+    }
+
+    #[test]
+    fn test_import_markdown_folder_and_day_one() {
+        let (vault, export, mut conn) = setup();
+        fs::create_dir_all(export.path().join("notes/sub")).unwrap();
+        fs::write(
+            export.path().join("notes/sub/idea.md"),
+            "---\ntags: [idea, draft]\n---\n\nBig idea",
+        )
+        .unwrap();
+        fs::write(export.path().join("notes/todo.txt"), "Plain text").unwrap();
+        fs::write(
+            export.path().join("notes/windows.md"),
+            "---\r\ntags: [home]\r\n---\r\nFix sink",
+        )
+        .unwrap();
+        fs::write(export.path().join("notes/image.png"), "not a note").unwrap();
+
+        let report = run_import(
+            vault.path(),
+            &mut conn,
+            ImportSource::Markdown,
+            &export.path().join("notes"),
+        );
+        assert_eq!(report.imported.len(), 3);
+        let contents: Vec<String> = index::get_all_jots(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|jot| jot.content)
+            .collect();
+        assert!(contents.contains(&"Big idea #idea #draft".to_string()));
+        assert!(contents.contains(&"Fix sink #home".to_string()));
+
+        let journal = export.path().join("Journal.json");
+        fs::write(
+            &journal,
+            r#"{"entries": [{"text": "Sunny day", "creationDate": "2020-06-01T08:00:00Z", "tags": ["weather"]}]}"#,
+        )
+        .unwrap();
+        let report = run_import(vault.path(), &mut conn, ImportSource::DayOne, &journal);
+        let jot = index::get_jot(&conn, &report.imported[0]).unwrap().unwrap();
+        assert_eq!(jot.content, "Sunny day #weather");
+        assert_eq!(jot.created_at.to_rfc3339(), "2020-06-01T08:00:00+00:00");
+    }
+
+    #[test]
+    fn test_import_applies_configured_content_rules() {
+        let (vault, export, mut conn) = setup();
+        fs::write(export.path().join("ok.md"), "Fine note").unwrap();
+        fs::write(export.path().join("secret.md"), "Top secret plan").unwrap();
+        fs::write(export.path().join("secret-copy.md"), "Top secret plan").unwrap();
+        let options = WriteOptions {
+            rules: ContentRules {
+                forbidden_words: vec!["secret".to_string()],
+                ..ContentRules::default()
+            },
+            ..WriteOptions::default()
+        };
+
+        let report = unit_of_work::run_with_options(&mut conn, vault.path(), options, |uow| {
+            import_jots(uow, ImportSource::Markdown, export.path(), HashSet::new())
+        })
+        .unwrap();
+        assert_eq!(report.imported.len(), 1);
+        // A rejected note is not a duplicate source for its copy
+        assert_eq!(report.duplicates, 0);
+        assert_eq!(report.failed.len(), 2);
+        assert!(report.failed[1].source.ends_with("secret.md"));
+    }
+}
@@ -1,14 +1,60 @@
-use crate::jots::models::{Jot, JotError};
-use rusqlite::{Connection, Result as SqlResult};
+use crate::jots::models::{
+    ArchivedFilter, ExternalLink, Jot, JotError, JotFilter, JotListQuery, JotPage, JotSortField,
+    SortDirection,
+};
+use crate::jots::parser::extract_embeds;
+use crate::jots::{reminders, tasks};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, OptionalExtension, Result as SqlResult, Row};
+use std::collections::BTreeMap;
+
+/// Columns read by `row_to_jot`
+const JOT_COLUMNS: &str =
+    "id, file_path, content, created_at, modified_at, tags, links, promoted, archived, properties";
+
+/// Map a jot_index row to Jot
+/// Expects columns: JOT_COLUMNS
+pub fn row_to_jot(row: &Row) -> SqlResult<Jot> {
+    let id: String = row.get(0)?;
+    let file_path: String = row.get(1)?;
+    let content: String = row.get(2)?;
+    let created_at_ms: i64 = row.get(3)?;
+    let modified_at_ms: i64 = row.get(4)?;
+    let tags_json: String = row.get(5)?;
+    let links_json: String = row.get(6)?;
+    let promoted: i32 = row.get(7)?;
+    let archived: i32 = row.get(8)?;
+    let properties_json: String = row.get(9)?;
+
+    let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
+    let links: Vec<String> = serde_json::from_str(&links_json).unwrap_or_default();
+    let properties = serde_json::from_str(&properties_json).unwrap_or_default();
+
+    Ok(Jot {
+        id,
+        content,
+        created_at: chrono::DateTime::from_timestamp_millis(created_at_ms).unwrap_or_default(),
+        modified_at: chrono::DateTime::from_timestamp_millis(modified_at_ms).unwrap_or_default(),
+        tags,
+        links,
+        promoted: promoted != 0,
+        archived: archived != 0,
+        external_links: vec![],
+        properties,
+        file_path,
+    })
+}
 
 /// Insert jot into SQLite index
 pub fn insert_jot(conn: &Connection, jot: &Jot) -> Result<(), JotError> {
     let tags_json = serde_json::to_string(&jot.tags).unwrap_or_else(|_| "[]".to_string());
     let links_json = serde_json::to_string(&jot.links).unwrap_or_else(|_| "[]".to_string());
+    let properties_json =
+        serde_json::to_string(&jot.properties).unwrap_or_else(|_| "{}".to_string());
 
     conn.execute(
-        "INSERT INTO jot_index (id, file_path, content, created_at, modified_at, tags, links, promoted, file_mtime)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        "INSERT INTO jot_index (id, file_path, content, created_at, modified_at, tags, links, promoted, archived, file_mtime, properties)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
         rusqlite::params![
             jot.id,
             jot.file_path,
@@ -18,10 +64,23 @@ pub fn insert_jot(conn: &Connection, jot: &Jot) -> Result<(), JotError> {
             tags_json,
             links_json,
             if jot.promoted { 1 } else { 0 },
+            if jot.archived { 1 } else { 0 },
             jot.modified_at.timestamp_millis(), // file_mtime = modified_at for consistency
+            properties_json,
         ],
     )?;
 
+    sync_jot_attachments(conn, jot)?;
+    sync_external_links(conn, jot)?;
+    sync_jot_properties(conn, &jot.id, &jot.properties)?;
+    tasks::sync_tasks(conn, &jot.id, &jot.content)?;
+    reminders::sync_reminders(
+        conn,
+        &jot.id,
+        &jot.content,
+        jot.modified_at,
+        chrono::Utc::now(),
+    )?;
     Ok(())
 }
 
@@ -29,10 +88,12 @@ pub fn insert_jot(conn: &Connection, jot: &Jot) -> Result<(), JotError> {
 pub fn update_jot_index(conn: &Connection, jot: &Jot) -> Result<(), JotError> {
     let tags_json = serde_json::to_string(&jot.tags).unwrap_or_else(|_| "[]".to_string());
     let links_json = serde_json::to_string(&jot.links).unwrap_or_else(|_| "[]".to_string());
+    let properties_json =
+        serde_json::to_string(&jot.properties).unwrap_or_else(|_| "{}".to_string());
 
     conn.execute(
-        "INSERT OR REPLACE INTO jot_index (id, file_path, content, created_at, modified_at, tags, links, promoted, file_mtime)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        "INSERT OR REPLACE INTO jot_index (id, file_path, content, created_at, modified_at, tags, links, promoted, archived, file_mtime, properties)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
         rusqlite::params![
             jot.id,
             jot.file_path,
@@ -42,106 +103,437 @@ pub fn update_jot_index(conn: &Connection, jot: &Jot) -> Result<(), JotError> {
             tags_json,
             links_json,
             if jot.promoted { 1 } else { 0 },
+            if jot.archived { 1 } else { 0 },
             jot.modified_at.timestamp_millis(),
+            properties_json,
         ],
     )?;
 
+    sync_jot_attachments(conn, jot)?;
+    sync_external_links(conn, jot)?;
+    sync_jot_properties(conn, &jot.id, &jot.properties)?;
+    tasks::sync_tasks(conn, &jot.id, &jot.content)?;
+    reminders::sync_reminders(
+        conn,
+        &jot.id,
+        &jot.content,
+        jot.modified_at,
+        chrono::Utc::now(),
+    )?;
+    Ok(())
+}
+
+/// Replace the jot_attachments rows of a jot with the ![[embeds]] in its content
+fn sync_jot_attachments(conn: &Connection, jot: &Jot) -> Result<(), JotError> {
+    conn.execute(
+        "DELETE FROM jot_attachments WHERE jot_id = ?1",
+        rusqlite::params![jot.id],
+    )?;
+
+    for file_name in extract_embeds(&jot.content) {
+        conn.execute(
+            "INSERT OR IGNORE INTO jot_attachments (jot_id, file_name) VALUES (?1, ?2)",
+            rusqlite::params![jot.id, file_name],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Replace the jot_links_external rows of a jot with its captured link metadata
+fn sync_external_links(conn: &Connection, jot: &Jot) -> Result<(), JotError> {
+    conn.execute(
+        "DELETE FROM jot_links_external WHERE jot_id = ?1",
+        rusqlite::params![jot.id],
+    )?;
+
+    for link in &jot.external_links {
+        conn.execute(
+            "INSERT OR REPLACE INTO jot_links_external
+             (jot_id, url, title, description, canonical_url, site_name, excerpt, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                jot.id,
+                link.url,
+                link.title,
+                link.description,
+                link.canonical_url,
+                link.site_name,
+                link.excerpt,
+                link.fetched_at.timestamp_millis(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Values of a property as matched by filters: one per list item; nested maps and nulls are skipped
+fn property_values(value: &serde_yaml::Value) -> Vec<String> {
+    match value {
+        serde_yaml::Value::String(text) => vec![text.clone()],
+        serde_yaml::Value::Number(number) => vec![number.to_string()],
+        serde_yaml::Value::Bool(flag) => vec![flag.to_string()],
+        serde_yaml::Value::Sequence(items) => items.iter().flat_map(property_values).collect(),
+        _ => vec![],
+    }
+}
+
+/// Replace the jot_properties rows of a jot with its custom properties
+pub fn sync_jot_properties(
+    conn: &Connection,
+    jot_id: &str,
+    properties: &BTreeMap<String, serde_yaml::Value>,
+) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM jot_properties WHERE jot_id = ?1",
+        rusqlite::params![jot_id],
+    )?;
+
+    for (key, value) in properties {
+        for text in property_values(value) {
+            conn.execute(
+                "INSERT OR IGNORE INTO jot_properties (jot_id, key, value) VALUES (?1, ?2, ?3)",
+                rusqlite::params![jot_id, key, text],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fill in `external_links` of jots loaded from jot_index
+fn load_external_links(conn: &Connection, jots: &mut [Jot]) -> Result<(), JotError> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT url, title, description, canonical_url, site_name, excerpt, fetched_at
+         FROM jot_links_external
+         WHERE jot_id = ?1
+         ORDER BY url",
+    )?;
+
+    for jot in jots.iter_mut() {
+        jot.external_links = stmt
+            .query_map(rusqlite::params![jot.id], |row| {
+                let fetched_at_ms: i64 = row.get(6)?;
+                Ok(ExternalLink {
+                    url: row.get(0)?,
+                    title: row.get(1)?,
+                    description: row.get(2)?,
+                    canonical_url: row.get(3)?,
+                    site_name: row.get(4)?,
+                    excerpt: row.get(5)?,
+                    fetched_at: chrono::DateTime::from_timestamp_millis(fetched_at_ms)
+                        .unwrap_or_default(),
+                })
+            })?
+            .collect::<SqlResult<Vec<ExternalLink>>>()?;
+    }
+
     Ok(())
 }
 
 /// Delete jot from SQLite index
 pub fn delete_jot_index(conn: &Connection, id: &str) -> Result<(), JotError> {
     conn.execute("DELETE FROM jot_index WHERE id = ?1", rusqlite::params![id])?;
+    conn.execute(
+        "DELETE FROM jot_attachments WHERE jot_id = ?1",
+        rusqlite::params![id],
+    )?;
+    conn.execute(
+        "DELETE FROM jot_links_external WHERE jot_id = ?1",
+        rusqlite::params![id],
+    )?;
+    conn.execute(
+        "DELETE FROM jot_properties WHERE jot_id = ?1",
+        rusqlite::params![id],
+    )?;
+    conn.execute(
+        "DELETE FROM jot_tasks WHERE jot_id = ?1",
+        rusqlite::params![id],
+    )?;
+    conn.execute(
+        "DELETE FROM reminders WHERE jot_id = ?1",
+        rusqlite::params![id],
+    )?;
     Ok(())
 }
 
-/// Get jots with pagination (oldest first)
-pub fn get_jots(conn: &Connection, limit: u32, offset: u32) -> Result<Vec<Jot>, JotError> {
-    let mut stmt = conn.prepare(
-        "SELECT id, file_path, content, created_at, modified_at, tags, links, promoted
-         FROM jot_index
-         ORDER BY created_at ASC
-         LIMIT ?1 OFFSET ?2",
+/// WHERE conditions (with `?` placeholders) and their values for a listing filter
+fn filter_conditions(filter: &JotFilter) -> (Vec<String>, Vec<Value>) {
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+
+    if let Some(promoted) = filter.promoted {
+        conditions.push("promoted = ?".to_string());
+        values.push(Value::Integer(if promoted { 1 } else { 0 }));
+    }
+
+    match filter.archived {
+        ArchivedFilter::Exclude => conditions.push("archived = 0".to_string()),
+        ArchivedFilter::Only => conditions.push("archived = 1".to_string()),
+        ArchivedFilter::Include => {}
+    }
+
+    if let Some(from) = filter.created_from {
+        conditions.push("created_at >= ?".to_string());
+        values.push(Value::Integer(from.timestamp_millis()));
+    }
+
+    if let Some(to) = filter.created_to {
+        conditions.push("created_at < ?".to_string());
+        values.push(Value::Integer(to.timestamp_millis()));
+    }
+
+    for tag in &filter.tags {
+        conditions.push("EXISTS (SELECT 1 FROM json_each(tags) WHERE value = ?)".to_string());
+        values.push(Value::Text(tag.trim_start_matches('#').to_string()));
+    }
+
+    for (key, value) in &filter.properties {
+        conditions.push(
+            "EXISTS (SELECT 1 FROM jot_properties
+                     WHERE jot_id = jot_index.id
+                     AND key = ? COLLATE NOCASE AND value = ? COLLATE NOCASE)"
+                .to_string(),
+        );
+        values.push(Value::Text(key.clone()));
+        values.push(Value::Text(value.trim().to_string()));
+    }
+
+    (conditions, values)
+}
+
+fn where_clause(conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    }
+}
+
+/// Cursor prefix tying a cursor to the ordering it was issued for
+fn cursor_prefix(sort: JotSortField, direction: SortDirection) -> &'static str {
+    match (sort, direction) {
+        (JotSortField::Created, SortDirection::Asc) => "ca",
+        (JotSortField::Created, SortDirection::Desc) => "cd",
+        (JotSortField::Modified, SortDirection::Asc) => "ma",
+        (JotSortField::Modified, SortDirection::Desc) => "md",
+    }
+}
+
+/// Encode the position after `jot` as an opaque (hex) cursor
+fn encode_cursor(jot: &Jot, sort: JotSortField, direction: SortDirection) -> String {
+    let key = match sort {
+        JotSortField::Created => jot.created_at.timestamp_millis(),
+        JotSortField::Modified => jot.modified_at.timestamp_millis(),
+    };
+    let raw = format!("{}:{}:{}", cursor_prefix(sort, direction), key, jot.id);
+    raw.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a cursor into (sort key, id), rejecting cursors issued for another ordering
+fn decode_cursor(
+    cursor: &str,
+    sort: JotSortField,
+    direction: SortDirection,
+) -> Result<(i64, String), JotError> {
+    let invalid = || JotError::validation("cursor", "Invalid or expired cursor");
+
+    if !cursor.len().is_multiple_of(2) || !cursor.is_ascii() {
+        return Err(invalid());
+    }
+    let bytes = (0..cursor.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&cursor[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| invalid())?;
+    let raw = String::from_utf8(bytes).map_err(|_| invalid())?;
+
+    let mut parts = raw.splitn(3, ':');
+    let (Some(prefix), Some(key), Some(id)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid());
+    };
+    if prefix != cursor_prefix(sort, direction) {
+        return Err(invalid());
+    }
+    let key = key.parse::<i64>().map_err(|_| invalid())?;
+
+    Ok((key, id.to_string()))
+}
+
+/// Get one page of jots using keyset pagination
+/// Pages stay stable while jots are added, since each continues after the last row seen
+pub fn get_jots(conn: &Connection, query: &JotListQuery) -> Result<JotPage, JotError> {
+    let column = match query.sort {
+        JotSortField::Created => "created_at",
+        JotSortField::Modified => "modified_at",
+    };
+    let (comparison, order) = match query.direction {
+        SortDirection::Asc => (">", "ASC"),
+        SortDirection::Desc => ("<", "DESC"),
+    };
+
+    let (mut conditions, mut values) = filter_conditions(&query.filter);
+
+    let total_count: u32 = conn.query_row(
+        &format!(
+            "SELECT COUNT(*) FROM jot_index {}",
+            where_clause(&conditions)
+        ),
+        params_from_iter(values.iter()),
+        |row| row.get(0),
     )?;
 
-    let jots = stmt
-        .query_map(rusqlite::params![limit, offset], |row| {
-            let id: String = row.get(0)?;
-            let file_path: String = row.get(1)?;
-            let content: String = row.get(2)?;
-            let created_at_ms: i64 = row.get(3)?;
-            let modified_at_ms: i64 = row.get(4)?;
-            let tags_json: String = row.get(5)?;
-            let links_json: String = row.get(6)?;
-            let promoted: i32 = row.get(7)?;
-
-            let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
-            let links: Vec<String> = serde_json::from_str(&links_json).unwrap_or_default();
-
-            Ok(Jot {
-                id,
-                content,
-                created_at: chrono::DateTime::from_timestamp_millis(created_at_ms)
-                    .unwrap_or_default(),
-                modified_at: chrono::DateTime::from_timestamp_millis(modified_at_ms)
-                    .unwrap_or_default(),
-                tags,
-                links,
-                promoted: promoted != 0,
-                file_path,
-            })
-        })?
+    if let Some(cursor) = &query.cursor {
+        let (key, id) = decode_cursor(cursor, query.sort, query.direction)?;
+        conditions.push(format!(
+            "({col} {cmp} ? OR ({col} = ? AND id {cmp} ?))",
+            col = column,
+            cmp = comparison
+        ));
+        values.extend([Value::Integer(key), Value::Integer(key), Value::Text(id)]);
+    }
+
+    // Fetch one extra row to know whether there is a next page
+    values.push(Value::Integer(i64::from(query.limit) + 1));
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}
+         FROM jot_index
+         {}
+         ORDER BY {col} {order}, id {order}
+         LIMIT ?",
+        JOT_COLUMNS,
+        where_clause(&conditions),
+        col = column,
+        order = order
+    ))?;
+
+    let mut jots = stmt
+        .query_map(params_from_iter(values.iter()), row_to_jot)?
+        .collect::<SqlResult<Vec<Jot>>>()?;
+
+    load_external_links(conn, &mut jots)?;
+
+    let next_cursor = if jots.len() > query.limit as usize {
+        jots.truncate(query.limit as usize);
+        jots.last()
+            .map(|jot| encode_cursor(jot, query.sort, query.direction))
+    } else {
+        None
+    };
+
+    Ok(JotPage {
+        jots,
+        next_cursor,
+        total_count,
+    })
+}
+
+/// Get every indexed jot (oldest first)
+pub fn get_all_jots(conn: &Connection) -> Result<Vec<Jot>, JotError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}
+         FROM jot_index
+         ORDER BY created_at ASC",
+        JOT_COLUMNS
+    ))?;
+
+    let mut jots = stmt
+        .query_map([], row_to_jot)?
+        .collect::<SqlResult<Vec<Jot>>>()?;
+    load_external_links(conn, &mut jots)?;
+
+    Ok(jots)
+}
+
+/// Get every indexed jot matching a listing filter (oldest first)
+pub fn get_filtered_jots(conn: &Connection, filter: &JotFilter) -> Result<Vec<Jot>, JotError> {
+    let (conditions, values) = filter_conditions(filter);
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}
+         FROM jot_index
+         {}
+         ORDER BY created_at ASC, id ASC",
+        JOT_COLUMNS,
+        where_clause(&conditions)
+    ))?;
+
+    let mut jots = stmt
+        .query_map(params_from_iter(values.iter()), row_to_jot)?
         .collect::<SqlResult<Vec<Jot>>>()?;
+    load_external_links(conn, &mut jots)?;
 
     Ok(jots)
 }
 
-/// Search jots by content (case-insensitive LIKE query)
-pub fn search_jots(conn: &Connection, query: &str, limit: u32) -> Result<Vec<Jot>, JotError> {
+/// Get a single indexed jot by id
+pub fn get_jot(conn: &Connection, id: &str) -> Result<Option<Jot>, JotError> {
+    let jot = conn
+        .query_row(
+            &format!(
+                "SELECT {}
+                 FROM jot_index
+                 WHERE id = ?1",
+                JOT_COLUMNS
+            ),
+            rusqlite::params![id],
+            row_to_jot,
+        )
+        .optional()?;
+
+    let mut jots: Vec<Jot> = jot.into_iter().collect();
+    load_external_links(conn, &mut jots)?;
+
+    Ok(jots.pop())
+}
+
+/// File path (relative to the vault) of an indexed jot
+pub fn get_file_path(conn: &Connection, id: &str) -> Result<Option<String>, JotError> {
+    Ok(conn
+        .query_row(
+            "SELECT file_path FROM jot_index WHERE id = ?1",
+            rusqlite::params![id],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+/// Search jots by content (case-insensitive LIKE query), optionally narrowed by a listing filter
+/// Without a filter the listing defaults apply, so archived jots are left out
+pub fn search_jots(
+    conn: &Connection,
+    query: &str,
+    filter: Option<&JotFilter>,
+    limit: u32,
+) -> Result<Vec<Jot>, JotError> {
     if query.trim().is_empty() {
-        return Err(JotError::ValidationError(
-            "Search query cannot be empty".to_string(),
+        return Err(JotError::validation(
+            "query",
+            "Search query cannot be empty",
         ));
     }
 
-    let search_pattern = format!("%{}%", query);
+    let (mut conditions, mut values) = filter_conditions(filter.unwrap_or(&JotFilter::default()));
+    conditions.insert(0, "content LIKE ?".to_string());
+    values.insert(0, Value::Text(format!("%{}%", query)));
+    values.push(Value::Integer(i64::from(limit)));
 
-    let mut stmt = conn.prepare(
-        "SELECT id, file_path, content, created_at, modified_at, tags, links, promoted
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}
          FROM jot_index
-         WHERE content LIKE ?1
+         {}
          ORDER BY created_at DESC
-         LIMIT ?2",
-    )?;
+         LIMIT ?",
+        JOT_COLUMNS,
+        where_clause(&conditions)
+    ))?;
 
-    let jots = stmt
-        .query_map(rusqlite::params![search_pattern, limit], |row| {
-            let id: String = row.get(0)?;
-            let file_path: String = row.get(1)?;
-            let content: String = row.get(2)?;
-            let created_at_ms: i64 = row.get(3)?;
-            let modified_at_ms: i64 = row.get(4)?;
-            let tags_json: String = row.get(5)?;
-            let links_json: String = row.get(6)?;
-            let promoted: i32 = row.get(7)?;
-
-            let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
-            let links: Vec<String> = serde_json::from_str(&links_json).unwrap_or_default();
-
-            Ok(Jot {
-                id,
-                content,
-                created_at: chrono::DateTime::from_timestamp_millis(created_at_ms)
-                    .unwrap_or_default(),
-                modified_at: chrono::DateTime::from_timestamp_millis(modified_at_ms)
-                    .unwrap_or_default(),
-                tags,
-                links,
-                promoted: promoted != 0,
-                file_path,
-            })
-        })?
+    let mut jots = stmt
+        .query_map(params_from_iter(values.iter()), row_to_jot)?
         .collect::<SqlResult<Vec<Jot>>>()?;
+    load_external_links(conn, &mut jots)?;
 
     Ok(jots)
 }
@@ -162,6 +554,9 @@ mod tests {
             tags: vec!["test".to_string()],
             links: vec![],
             promoted: false,
+            archived: false,
+            external_links: vec![],
+            properties: Default::default(),
             file_path: format!(".scribel/jots/{}.md", id),
         }
     }
@@ -177,7 +572,7 @@ mod tests {
         insert_jot(&conn, &jot1).unwrap();
         insert_jot(&conn, &jot2).unwrap();
 
-        let jots = get_jots(&conn, 10, 0).unwrap();
+        let jots = get_jots(&conn, &JotListQuery::default()).unwrap().jots;
         assert_eq!(jots.len(), 2);
         assert_eq!(jots[0].id, "jot-1");
         assert_eq!(jots[1].id, "jot-2");
@@ -194,7 +589,7 @@ mod tests {
         jot.content = "Updated content".to_string();
         update_jot_index(&conn, &jot).unwrap();
 
-        let jots = get_jots(&conn, 10, 0).unwrap();
+        let jots = get_jots(&conn, &JotListQuery::default()).unwrap().jots;
         assert_eq!(jots.len(), 1);
         assert_eq!(jots[0].content, "Updated content");
     }
@@ -209,7 +604,7 @@ mod tests {
 
         delete_jot_index(&conn, &jot.id).unwrap();
 
-        let jots = get_jots(&conn, 10, 0).unwrap();
+        let jots = get_jots(&conn, &JotListQuery::default()).unwrap().jots;
         assert!(jots.is_empty());
     }
 
@@ -224,11 +619,33 @@ mod tests {
         insert_jot(&conn, &jot1).unwrap();
         insert_jot(&conn, &jot2).unwrap();
 
-        let results = search_jots(&conn, "meeting", 10).unwrap();
+        let results = search_jots(&conn, "meeting", None, 10).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, "jot-1");
     }
 
+    #[test]
+    fn test_search_excludes_archived_by_default() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        let mut archived = create_test_jot("jot-1", "Old meeting", 0);
+        archived.archived = true;
+        insert_jot(&conn, &archived).unwrap();
+        insert_jot(&conn, &create_test_jot("jot-2", "New meeting", 1)).unwrap();
+
+        let results = search_jots(&conn, "meeting", None, 10).unwrap();
+        let ids: Vec<&str> = results.iter().map(|j| j.id.as_str()).collect();
+        assert_eq!(ids, vec!["jot-2"]);
+
+        let filter = JotFilter {
+            archived: ArchivedFilter::Include,
+            ..Default::default()
+        };
+        let results = search_jots(&conn, "meeting", Some(&filter), 10).unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
     #[test]
     fn test_search_case_insensitive() {
         let conn = Connection::open_in_memory().unwrap();
@@ -237,7 +654,7 @@ mod tests {
         let jot = create_test_jot("jot-1", "Important Meeting", 0);
         insert_jot(&conn, &jot).unwrap();
 
-        let results = search_jots(&conn, "meeting", 10).unwrap();
+        let results = search_jots(&conn, "meeting", None, 10).unwrap();
         assert_eq!(results.len(), 1);
     }
 
@@ -251,12 +668,217 @@ mod tests {
             insert_jot(&conn, &jot).unwrap();
         }
 
-        let page1 = get_jots(&conn, 2, 0).unwrap();
-        assert_eq!(page1.len(), 2);
-        assert_eq!(page1[0].id, "jot-0");
+        let mut query = JotListQuery {
+            limit: 2,
+            ..Default::default()
+        };
+        let page1 = get_jots(&conn, &query).unwrap();
+        assert_eq!(page1.jots.len(), 2);
+        assert_eq!(page1.jots[0].id, "jot-0");
+        assert_eq!(page1.total_count, 5);
+
+        query.cursor = page1.next_cursor;
+        let page2 = get_jots(&conn, &query).unwrap();
+        assert_eq!(page2.jots.len(), 2);
+        assert_eq!(page2.jots[0].id, "jot-2");
+
+        query.cursor = page2.next_cursor;
+        let page3 = get_jots(&conn, &query).unwrap();
+        assert_eq!(page3.jots.len(), 1);
+        assert_eq!(page3.jots[0].id, "jot-4");
+        assert!(page3.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_pagination_stable_under_inserts() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        for i in 0..4 {
+            let jot = create_test_jot(&format!("jot-{}", i), &format!("Jot {}", i), i);
+            insert_jot(&conn, &jot).unwrap();
+        }
+
+        let mut query = JotListQuery {
+            sort: JotSortField::Created,
+            direction: SortDirection::Desc,
+            limit: 2,
+            ..Default::default()
+        };
+        let page1 = get_jots(&conn, &query).unwrap();
+        assert_eq!(page1.jots[0].id, "jot-3");
+
+        // A new jot at the top must not shift the next page
+        insert_jot(&conn, &create_test_jot("jot-new", "Newest", 10)).unwrap();
+
+        query.cursor = page1.next_cursor;
+        let page2 = get_jots(&conn, &query).unwrap();
+        let ids: Vec<&str> = page2.jots.iter().map(|j| j.id.as_str()).collect();
+        assert_eq!(ids, vec!["jot-1", "jot-0"]);
+        assert_eq!(page2.total_count, 5);
+    }
+
+    #[test]
+    fn test_sort_by_modified_and_filter_promoted() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        let mut old = create_test_jot("jot-old", "Created first", 0);
+        old.modified_at = old.created_at + chrono::Duration::seconds(60);
+        old.promoted = true;
+        insert_jot(&conn, &old).unwrap();
+        insert_jot(&conn, &create_test_jot("jot-new", "Created second", 1)).unwrap();
+
+        let query = JotListQuery {
+            sort: JotSortField::Modified,
+            direction: SortDirection::Desc,
+            ..Default::default()
+        };
+        let page = get_jots(&conn, &query).unwrap();
+        assert_eq!(page.jots[0].id, "jot-old");
+
+        let query = JotListQuery {
+            filter: JotFilter {
+                promoted: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let page = get_jots(&conn, &query).unwrap();
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.jots[0].id, "jot-new");
+    }
+
+    #[test]
+    fn test_archived_hidden_by_default() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        let mut archived = create_test_jot("jot-archived", "Done", 0);
+        archived.archived = true;
+        insert_jot(&conn, &archived).unwrap();
+        insert_jot(&conn, &create_test_jot("jot-inbox", "Open", 1)).unwrap();
+
+        let page = get_jots(&conn, &JotListQuery::default()).unwrap();
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.jots[0].id, "jot-inbox");
+
+        let mut query = JotListQuery::default();
+        query.filter.archived = ArchivedFilter::Only;
+        let page = get_jots(&conn, &query).unwrap();
+        assert_eq!(page.jots.len(), 1);
+        assert!(page.jots[0].archived);
+
+        query.filter.archived = ArchivedFilter::Include;
+        assert_eq!(get_jots(&conn, &query).unwrap().total_count, 2);
+    }
+
+    #[test]
+    fn test_filter_by_date_range_and_tags() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        let mut tagged = create_test_jot("jot-tagged", "Plan #work #idea", -3600);
+        tagged.tags = vec!["work".to_string(), "idea".to_string()];
+        insert_jot(&conn, &tagged).unwrap();
+        insert_jot(&conn, &create_test_jot("jot-old", "Old", -7200)).unwrap();
+        insert_jot(&conn, &create_test_jot("jot-new", "New", 0)).unwrap();
+
+        let filter = JotFilter {
+            tags: vec!["work".to_string(), "#idea".to_string()],
+            ..Default::default()
+        };
+        let ids: Vec<String> = get_filtered_jots(&conn, &filter)
+            .unwrap()
+            .into_iter()
+            .map(|jot| jot.id)
+            .collect();
+        assert_eq!(ids, vec!["jot-tagged"]);
+
+        let filter = JotFilter {
+            created_from: Some(tagged.created_at),
+            created_to: Some(Utc::now() - chrono::Duration::seconds(60)),
+            ..Default::default()
+        };
+        let ids: Vec<String> = get_filtered_jots(&conn, &filter)
+            .unwrap()
+            .into_iter()
+            .map(|jot| jot.id)
+            .collect();
+        assert_eq!(ids, vec!["jot-tagged"]);
+    }
+
+    #[test]
+    fn test_filter_and_search_by_properties() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        let mut apollo = create_test_jot("jot-apollo", "Launch plan", 0);
+        apollo.properties.insert(
+            "project".to_string(),
+            serde_yaml::from_str("[Apollo, Gemini]").unwrap(),
+        );
+        apollo
+            .properties
+            .insert("priority".to_string(), serde_yaml::Value::from(1));
+        insert_jot(&conn, &apollo).unwrap();
+        insert_jot(&conn, &create_test_jot("jot-other", "Launch party", 1)).unwrap();
+
+        let fetched = get_jot(&conn, "jot-apollo").unwrap().unwrap();
+        assert_eq!(fetched.properties, apollo.properties);
+
+        let filter = JotFilter {
+            properties: [
+                ("Project".to_string(), "gemini".to_string()),
+                ("priority".to_string(), "1".to_string()),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let page = get_jots(
+            &conn,
+            &JotListQuery {
+                filter: filter.clone(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.jots[0].id, "jot-apollo");
+
+        let results = search_jots(&conn, "launch", Some(&filter), 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "jot-apollo");
+        assert_eq!(search_jots(&conn, "launch", None, 10).unwrap().len(), 2);
+
+        delete_jot_index(&conn, "jot-apollo").unwrap();
+        let rows: u32 = conn
+            .query_row("SELECT COUNT(*) FROM jot_properties", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(rows, 0);
+    }
+
+    #[test]
+    fn test_cursor_rejected_for_other_ordering() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        for i in 0..3 {
+            insert_jot(&conn, &create_test_jot(&format!("jot-{}", i), "Jot", i)).unwrap();
+        }
+
+        let mut query = JotListQuery {
+            limit: 1,
+            ..Default::default()
+        };
+        query.cursor = get_jots(&conn, &query).unwrap().next_cursor;
+        query.direction = SortDirection::Desc;
+
+        let result = get_jots(&conn, &query);
+        assert!(matches!(result, Err(JotError::ValidationError { .. })));
 
-        let page2 = get_jots(&conn, 2, 2).unwrap();
-        assert_eq!(page2.len(), 2);
-        assert_eq!(page2[0].id, "jot-2");
+        query.cursor = Some("not a cursor".to_string());
+        let result = get_jots(&conn, &query);
+        assert!(matches!(result, Err(JotError::ValidationError { .. })));
     }
 }
@@ -0,0 +1,179 @@
+use crate::jots::history::matching_lines;
+use crate::jots::models::{Jot, JotError, MergeResult, UpdateJotInput};
+
+/// Conflict markers written around unresolved regions
+const MARKER_OURS: &str = "<<<<<<< yours";
+const MARKER_SEPARATOR: &str = "=======";
+const MARKER_THEIRS: &str = ">>>>>>> theirs";
+
+/// Fail with JotError::Conflict if the jot on disk no longer matches what the client last saw
+/// Only the fields present in `input` are checked; with neither, the update is unconditional
+pub fn detect_conflict(current: &Jot, input: &UpdateJotInput) -> Result<(), JotError> {
+    let modified_changed = input
+        .base_modified_at
+        .is_some_and(|base| base.timestamp_millis() != current.modified_at.timestamp_millis());
+
+    // External editors rarely touch the `modified` frontmatter, so compare content too
+    let content_changed = input
+        .base_content
+        .as_deref()
+        .is_some_and(|base| base.trim() != current.content);
+
+    if modified_changed || content_changed {
+        return Err(JotError::Conflict(current.id.clone()));
+    }
+
+    Ok(())
+}
+
+/// Line-based three-way merge of two edits (`ours`, `theirs`) of a common `base`
+/// Non-overlapping changes are combined; overlapping ones are wrapped in conflict markers
+pub fn three_way_merge(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    let base_lines: Vec<&str> = base.lines().collect();
+    let our_lines: Vec<&str> = ours.lines().collect();
+    let their_lines: Vec<&str> = theirs.lines().collect();
+
+    // For every base line: where it sits in ours / theirs, if it survived there
+    let mut in_ours = vec![None; base_lines.len()];
+    for (b, o) in matching_lines(&base_lines, &our_lines) {
+        in_ours[b] = Some(o);
+    }
+    let mut in_theirs = vec![None; base_lines.len()];
+    for (b, t) in matching_lines(&base_lines, &their_lines) {
+        in_theirs[b] = Some(t);
+    }
+
+    let mut merged: Vec<&str> = Vec::new();
+    let mut has_conflicts = false;
+    let (mut b, mut o, mut t) = (0, 0, 0);
+
+    // Base lines kept by both sides are stable anchors; resolve the chunks between them
+    let anchors = (0..base_lines.len())
+        .filter_map(|i| Some((i, in_ours[i]?, in_theirs[i]?)))
+        .chain(std::iter::once((
+            base_lines.len(),
+            our_lines.len(),
+            their_lines.len(),
+        )));
+
+    for (anchor_b, anchor_o, anchor_t) in anchors {
+        let base_chunk = &base_lines[b..anchor_b];
+        let our_chunk = &our_lines[o..anchor_o];
+        let their_chunk = &their_lines[t..anchor_t];
+
+        if our_chunk == their_chunk || their_chunk == base_chunk {
+            merged.extend_from_slice(our_chunk);
+        } else if our_chunk == base_chunk {
+            merged.extend_from_slice(their_chunk);
+        } else {
+            has_conflicts = true;
+            merged.push(MARKER_OURS);
+            merged.extend_from_slice(our_chunk);
+            merged.push(MARKER_SEPARATOR);
+            merged.extend_from_slice(their_chunk);
+            merged.push(MARKER_THEIRS);
+        }
+
+        if anchor_b < base_lines.len() {
+            merged.push(base_lines[anchor_b]);
+        }
+        b = anchor_b + 1;
+        o = anchor_o + 1;
+        t = anchor_t + 1;
+    }
+
+    MergeResult {
+        content: merged.join("\n"),
+        has_conflicts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn create_test_jot(content: &str) -> Jot {
+        let now = Utc::now();
+        Jot {
+            id: "jot-1".to_string(),
+            content: content.to_string(),
+            created_at: now,
+            modified_at: now,
+            tags: vec![],
+            links: vec![],
+            promoted: false,
+            archived: false,
+            external_links: vec![],
+            properties: Default::default(),
+            file_path: ".scribel/jots/jot-1.md".to_string(),
+        }
+    }
+
+    fn input(
+        base_modified_at: Option<chrono::DateTime<Utc>>,
+        base_content: Option<&str>,
+    ) -> UpdateJotInput {
+        UpdateJotInput {
+            id: "jot-1".to_string(),
+            content: "New".to_string(),
+            base_modified_at,
+            base_content: base_content.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_detect_conflict() {
+        let current = create_test_jot("On disk");
+
+        assert!(detect_conflict(&current, &input(None, None)).is_ok());
+        assert!(
+            detect_conflict(&current, &input(Some(current.modified_at), Some("On disk"))).is_ok()
+        );
+
+        let stale = current.modified_at - Duration::seconds(5);
+        assert!(matches!(
+            detect_conflict(&current, &input(Some(stale), None)),
+            Err(JotError::Conflict(_))
+        ));
+        assert!(matches!(
+            detect_conflict(&current, &input(None, Some("What I saw"))),
+            Err(JotError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn test_merge_non_overlapping_changes() {
+        let base = "line 1\nline 2\nline 3";
+        let ours = "line 1 (mine)\nline 2\nline 3";
+        let theirs = "line 1\nline 2\nline 3 (theirs)";
+
+        let result = three_way_merge(base, ours, theirs);
+        assert!(!result.has_conflicts);
+        assert_eq!(result.content, "line 1 (mine)\nline 2\nline 3 (theirs)");
+    }
+
+    #[test]
+    fn test_merge_additions_at_both_ends() {
+        let result = three_way_merge("middle", "top\nmiddle", "middle\nbottom");
+        assert!(!result.has_conflicts);
+        assert_eq!(result.content, "top\nmiddle\nbottom");
+    }
+
+    #[test]
+    fn test_merge_identical_changes() {
+        let result = three_way_merge("a\nb", "a\nc", "a\nc");
+        assert!(!result.has_conflicts);
+        assert_eq!(result.content, "a\nc");
+    }
+
+    #[test]
+    fn test_merge_conflict_markers() {
+        let result = three_way_merge("keep\nshared", "keep\nmine", "keep\ntheirs");
+        assert!(result.has_conflicts);
+        assert_eq!(
+            result.content,
+            "keep\n<<<<<<< yours\nmine\n=======\ntheirs\n>>>>>>> theirs"
+        );
+    }
+}
@@ -3,6 +3,30 @@ pub mod parser;
 pub mod storage;
 pub mod index;
 pub mod watcher;
+pub mod trash;
+pub mod history;
+pub mod atomic;
+pub mod unit_of_work;
+pub mod consistency;
+pub mod merge;
+pub mod stats;
+pub mod review;
+pub mod bulk;
+pub mod combine;
+pub mod export;
+pub mod import;
+pub mod html;
+pub mod attachments;
+pub mod web_clip;
+pub mod validation;
+pub mod ids;
+pub mod filenames;
+pub mod frontmatter;
+pub mod tasks;
+pub mod reminders;
+pub mod templates;
+#[cfg(test)]
+mod test_support;
 
 // Re-export main types
-pub use models::{CreateJotInput, Jot, JotError, JotFrontmatter, UpdateJotInput};
+pub use models::{CreateJotInput, Jot, JotError, JotFrontmatter, TrashedJot, UpdateJotInput};
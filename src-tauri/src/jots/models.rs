@@ -1,5 +1,6 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 /// Jot as represented in memory and API responses
@@ -26,10 +27,49 @@ pub struct Jot {
     /// Whether jot has been promoted to a full note
     pub promoted: bool,
 
+    /// Whether jot was processed and put away without becoming a note
+    #[serde(default)]
+    pub archived: bool,
+
+    /// Metadata fetched for URLs in the content
+    #[serde(default)]
+    pub external_links: Vec<ExternalLink>,
+
+    /// Custom properties: frontmatter keys beyond the built-in ones and inline
+    /// `key:: value` fields of the content (inline fields win)
+    #[serde(default)]
+    pub properties: BTreeMap<String, serde_yaml::Value>,
+
     /// Relative path from vault root
     pub file_path: String,
 }
 
+/// Metadata of a web page linked from a jot (OpenGraph, <title>, excerpt)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternalLink {
+    /// URL as it appears in the jot
+    pub url: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Canonical URL declared by the page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub site_name: Option<String>,
+
+    /// Start of the page's main text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+
+    pub fetched_at: DateTime<Utc>,
+}
+
 /// Input for creating a new jot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateJotInput {
@@ -41,6 +81,523 @@ pub struct CreateJotInput {
 pub struct UpdateJotInput {
     pub id: String,
     pub content: String,
+
+    /// modified_at of the version the client edited; update fails with a conflict if it changed
+    #[serde(default)]
+    pub base_modified_at: Option<DateTime<Utc>>,
+
+    /// Content of the version the client edited; update fails with a conflict if it changed
+    /// (also the common ancestor for a three-way merge)
+    #[serde(default)]
+    pub base_content: Option<String>,
+}
+
+/// Field jot listings are sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JotSortField {
+    #[default]
+    Created,
+    Modified,
+}
+
+/// Sort direction for jot listings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// How a listing treats archived jots
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchivedFilter {
+    /// Hide archived jots
+    #[default]
+    Exclude,
+    /// Archived and unarchived jots
+    Include,
+    /// Only archived jots
+    Only,
+}
+
+/// Which jots a listing includes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JotFilter {
+    /// Only promoted (true) or unpromoted (false) jots; all jots when unset
+    #[serde(default)]
+    pub promoted: Option<bool>,
+
+    /// Archived jots are hidden unless asked for
+    #[serde(default)]
+    pub archived: ArchivedFilter,
+
+    /// Only jots created at or after this time
+    #[serde(default)]
+    pub created_from: Option<DateTime<Utc>>,
+
+    /// Only jots created before this time
+    #[serde(default)]
+    pub created_to: Option<DateTime<Utc>>,
+
+    /// Only jots having every one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Only jots having every one of these property values (case-insensitive;
+    /// a list property matches if any of its items does)
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+}
+
+/// Query for one page of jots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JotListQuery {
+    #[serde(default)]
+    pub sort: JotSortField,
+
+    #[serde(default)]
+    pub direction: SortDirection,
+
+    #[serde(default, flatten)]
+    pub filter: JotFilter,
+
+    /// `next_cursor` of the previous page; first page when unset
+    #[serde(default)]
+    pub cursor: Option<String>,
+
+    /// Maximum number of jots in the page
+    #[serde(default = "default_page_size")]
+    pub limit: u32,
+}
+
+fn default_page_size() -> u32 {
+    50
+}
+
+impl Default for JotListQuery {
+    fn default() -> Self {
+        Self {
+            sort: JotSortField::default(),
+            direction: SortDirection::default(),
+            filter: JotFilter::default(),
+            cursor: None,
+            limit: default_page_size(),
+        }
+    }
+}
+
+/// One page of a jot listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JotPage {
+    pub jots: Vec<Jot>,
+
+    /// Opaque cursor for the next page; None on the last page
+    pub next_cursor: Option<String>,
+
+    /// Number of jots matching the filter, across all pages
+    pub total_count: u32,
+}
+
+/// Bucket size for timeline statistics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    #[default]
+    Day,
+    /// ISO weeks, starting on Monday
+    Week,
+    Month,
+}
+
+/// Range and bucketing for jot statistics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsQuery {
+    #[serde(default)]
+    pub granularity: Granularity,
+
+    /// Offset of the user's local time from UTC, in minutes (e.g. 120 for UTC+2)
+    /// Days, weeks and months are bucketed in this local time; without it, in the
+    /// system time zone (daylight saving changes included)
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+
+    /// Only jots created at or after this instant
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+
+    /// Only jots created before this instant
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Jots created in one timeline bucket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineBucket {
+    /// First local day of the bucket (YYYY-MM-DD)
+    pub start: NaiveDate,
+
+    /// Jots created in the bucket
+    pub count: u32,
+
+    /// Of those, jots that are promoted
+    pub promoted: u32,
+}
+
+/// Per-bucket counts for one tag
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagSeries {
+    pub tag: String,
+
+    /// Jots with the tag over the whole range
+    pub total: u32,
+
+    /// Jots with the tag per bucket, aligned with TagFrequency::buckets
+    pub counts: Vec<u32>,
+}
+
+/// Tag usage over time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagFrequency {
+    /// Bucket start dates (YYYY-MM-DD), including empty buckets
+    pub buckets: Vec<NaiveDate>,
+
+    /// Most used tags first
+    pub series: Vec<TagSeries>,
+}
+
+/// Summary statistics over a range of jots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JotStats {
+    pub total_jots: u32,
+    pub promoted_jots: u32,
+
+    /// promoted_jots / total_jots (0 with no jots)
+    pub promotion_rate: f64,
+
+    /// Mean time from creation to promotion; None when nothing was promoted
+    pub average_hours_to_promotion: Option<f64>,
+
+    /// Consecutive local days with at least one jot, ending today (or yesterday)
+    pub current_streak_days: u32,
+
+    /// Longest run of consecutive local days with at least one jot
+    pub longest_streak_days: u32,
+}
+
+/// Review state of a jot in the daily review queue, also the outcome of reviewing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewStatus {
+    /// Looked at; comes back after the next spaced interval
+    Seen,
+    /// Put off; comes back after the snooze period without advancing the interval
+    Snoozed,
+    /// Never resurfaced again
+    Archived,
+    /// Promoted to a note during review
+    Promoted,
+}
+
+/// Scheduling state of a reviewed jot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewState {
+    pub jot_id: String,
+    pub status: ReviewStatus,
+
+    /// Times the jot was marked seen
+    pub review_count: u32,
+
+    /// Current spaced-repetition interval
+    pub interval_days: u32,
+
+    pub last_reviewed_at: DateTime<Utc>,
+
+    /// When the jot is due to resurface (ISO 8601)
+    pub due_at: DateTime<Utc>,
+}
+
+/// Jot in today's review queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewItem {
+    pub jot: Jot,
+
+    /// Previous review state; None if never reviewed
+    pub review: Option<ReviewState>,
+
+    /// Priority in the queue (higher first)
+    pub score: f64,
+}
+
+/// Priority of a checkbox task (Obsidian Tasks markers 🔺 ⏫ 🔼 🔽 ⏬, or `priority: high`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskPriority {
+    Lowest,
+    Low,
+    /// No priority marker
+    #[default]
+    Normal,
+    Medium,
+    High,
+    Highest,
+}
+
+/// Checkbox task (`- [ ] call Bob`) inside a jot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JotTask {
+    pub jot_id: String,
+
+    /// Line of the jot content the task is on (0-based)
+    pub line: u32,
+
+    /// Task text without the checkbox, due date and priority markers
+    pub text: String,
+
+    pub done: bool,
+
+    /// Due date (`📅 2025-01-20` or `due: 2025-01-20`)
+    pub due: Option<NaiveDate>,
+
+    pub priority: TaskPriority,
+}
+
+/// Which tasks a task listing includes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatusFilter {
+    /// Not done yet
+    #[default]
+    Open,
+    /// Not done and due before today
+    Overdue,
+    Done,
+    All,
+}
+
+/// Query for tasks across jots
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskQuery {
+    #[serde(default)]
+    pub status: TaskStatusFilter,
+
+    /// The user's local date, which overdue is judged against; today (local time) when unset
+    #[serde(default)]
+    pub today: Option<NaiveDate>,
+
+    /// Tasks of archived jots are hidden unless asked for
+    #[serde(default)]
+    pub archived: ArchivedFilter,
+}
+
+/// Delivery state of a reminder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReminderStatus {
+    /// Waiting for remind_at
+    Pending,
+    /// Notified; stays listed until dismissed or snoozed
+    Fired,
+    /// Done with (also reminders already past when first found in a jot)
+    Dismissed,
+}
+
+/// Reminder extracted from a jot line such as "remind me to review PR tomorrow at 3pm"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: i64,
+    pub jot_id: String,
+
+    /// What to be reminded of ("review PR")
+    pub text: String,
+
+    /// When the jot said (ISO 8601)
+    pub due_at: DateTime<Utc>,
+
+    /// When the reminder fires: due_at, or later once snoozed (ISO 8601)
+    pub remind_at: DateTime<Utc>,
+
+    pub status: ReminderStatus,
+}
+
+/// Jot template stored as a Markdown file in the vault's templates folder
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JotTemplate {
+    /// File name without .md ("Meeting")
+    pub name: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Tags added to every jot created from the template (without '#')
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Template body with {{date}}, {{time}}, {{clipboard}}, {{cursor}} and {{prompt:Label}}
+    pub content: String,
+
+    /// Labels of the template's {{prompt:Label}} placeholders, in order of appearance
+    #[serde(default)]
+    pub prompts: Vec<String>,
+}
+
+/// Values for a template's placeholders when creating a jot from it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateValues {
+    /// Answers by prompt label; unanswered prompts are left empty
+    #[serde(default)]
+    pub prompts: BTreeMap<String, String>,
+
+    /// Clipboard text for {{clipboard}} (read by the frontend)
+    #[serde(default)]
+    pub clipboard: Option<String>,
+}
+
+/// Jot created from a template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateJot {
+    pub jot: Jot,
+
+    /// Where {{cursor}} was, as an offset into the jot content in UTF-16 code units
+    pub cursor: Option<usize>,
+}
+
+/// Jots a bulk operation applies to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkTarget {
+    /// Explicit list of jot IDs
+    Ids(Vec<String>),
+    /// Every jot whose content matches the search query
+    Query(String),
+}
+
+/// Operation applied to each jot of a bulk target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BulkOperation {
+    /// Move to the trash
+    Delete,
+    Promote,
+    Unpromote,
+    /// Append #tag to the content if not already present
+    AddTag {
+        tag: String,
+    },
+    /// Remove every #tag occurrence from the content
+    RemoveTag {
+        tag: String,
+    },
+    /// Move the file into a folder under .scribel/jots/ ("" for the jots folder itself)
+    MoveToFolder {
+        folder: String,
+    },
+    /// Copy the file into a directory outside the vault
+    Export {
+        destination: String,
+    },
+}
+
+/// What happens to the original jots after a merge
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeOriginals {
+    /// Move them to the trash
+    #[default]
+    Trash,
+    /// Keep them; the merged jot links to each with [[id]]
+    Link,
+}
+
+/// Output format of a jot export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// One combined Markdown file
+    Markdown,
+    /// JSON array of jots
+    Json,
+    Csv,
+    /// Static HTML page
+    Html,
+    /// Zip archive of the raw jot files (frontmatter included)
+    Zip,
+}
+
+/// Where an export was written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSummary {
+    pub path: String,
+    pub format: ExportFormat,
+
+    /// Number of jots exported
+    pub count: u32,
+}
+
+/// Tool an import comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportSource {
+    /// Folder of HTML files exported from Apple Notes
+    AppleNotes,
+    /// Keep folder of a Google Takeout export (one JSON file per note)
+    GoogleKeep,
+    /// Drafts JSON export (one file)
+    Drafts,
+    /// Folder of plain text / Markdown files
+    Markdown,
+    /// Day One JSON export (one file)
+    DayOne,
+}
+
+/// Note that could not be imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportFailure {
+    /// File (and entry, for multi-note files) the note came from
+    pub source: String,
+    pub message: String,
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// IDs of the jots created
+    pub imported: Vec<String>,
+
+    /// Notes whose content matched an existing jot (or an earlier note in the import)
+    pub duplicates: u32,
+
+    /// Empty or trashed notes
+    pub skipped: u32,
+
+    pub failed: Vec<ImportFailure>,
+}
+
+/// File stored in the vault's attachment folder and embedded in jots as ![[file_name]]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    /// Unique name, as used in embeds
+    pub file_name: String,
+
+    /// Path relative to the vault root
+    pub file_path: String,
+
+    /// Size in bytes
+    pub size: u64,
+
+    pub created_at: DateTime<Utc>,
+}
+
+/// Result of a three-way merge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeResult {
+    /// Merged text, with conflict markers around unresolved regions
+    pub content: String,
+
+    /// Whether the content contains conflict markers for the user to resolve
+    pub has_conflicts: bool,
 }
 
 /// Jot frontmatter structure (for YAML serialization)
@@ -52,6 +609,112 @@ pub struct JotFrontmatter {
     pub tags: Vec<String>,
     pub links: Vec<String>,
     pub promoted: bool,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_links: Vec<ExternalLink>,
+
+    /// Custom properties, written after the built-in keys (see FrontmatterSchema)
+    #[serde(skip)]
+    pub properties: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Jot sitting in the trash, awaiting restore or purge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedJot {
+    /// The jot as it was when deleted (file_path is its original location)
+    pub jot: Jot,
+
+    /// When the jot was moved to the trash (ISO 8601)
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Prior version of a jot, captured before it was modified
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JotRevision {
+    /// Revision number (increases with every recorded change)
+    pub revision_id: i64,
+
+    /// The jot this revision belongs to
+    pub jot_id: String,
+
+    /// Jot text at this revision
+    pub content: String,
+
+    /// Tags at this revision
+    pub tags: Vec<String>,
+
+    /// Wiki-links at this revision
+    pub links: Vec<String>,
+
+    /// Promoted status at this revision
+    pub promoted: bool,
+
+    /// When the jot was created (ISO 8601)
+    pub created_at: DateTime<Utc>,
+
+    /// When this version of the jot was last modified (ISO 8601)
+    pub modified_at: DateTime<Utc>,
+
+    /// When this revision was recorded, i.e. when it was replaced (ISO 8601)
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Kind of change for a line in a revision diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// Single line of a line-based diff between two revisions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// Drift found between jot files and jot_index
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsistencyReport {
+    /// Number of jot files scanned
+    pub checked_files: u32,
+
+    /// Jot files with no index row
+    pub missing_from_index: Vec<String>,
+
+    /// Index rows whose file no longer exists
+    pub orphaned_in_index: Vec<String>,
+
+    /// Index rows that differ from their file
+    pub out_of_date: Vec<String>,
+
+    /// Jot files (vault-relative paths) that could not be parsed (left untouched)
+    pub unreadable: Vec<String>,
+
+    /// Whether the index was repaired
+    pub repaired: bool,
+}
+
+impl ConsistencyReport {
+    /// Whether the index needs repair
+    pub fn has_drift(&self) -> bool {
+        !self.missing_from_index.is_empty()
+            || !self.orphaned_in_index.is_empty()
+            || !self.out_of_date.is_empty()
+    }
+}
+
+/// Content rule a jot failed (see validation::ContentRules)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationRule {
+    NotEmpty,
+    MaxLength,
+    RequiredTag,
+    ForbiddenWord,
 }
 
 /// Errors that can occur during jot operations
@@ -60,28 +723,58 @@ pub enum JotError {
     #[error("Jot not found: {0}")]
     NotFound(String),
 
+    #[error("Jot already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("Jot was modified since it was loaded: {0}")]
+    Conflict(String),
+
     #[error("File operation failed: {0}")]
     FileError(#[from] std::io::Error),
 
-    #[error("Invalid jot format: {0}")]
-    ParseError(String),
+    #[error("Invalid jot format in {file}: {message}")]
+    ParseError { file: String, message: String },
 
     #[error("Database error: {0}")]
     DbError(#[from] rusqlite::Error),
 
+    #[error("Database unavailable: {0}")]
+    DbUnavailable(#[from] crate::db::PoolError),
+
     #[error("Vault not configured")]
     VaultNotConfigured,
 
-    #[error("Invalid content: {0}")]
-    ValidationError(String),
+    #[error("Invalid {field}: {message}")]
+    ValidationError {
+        field: String,
+        message: String,
+        /// Content rule that failed, when one did
+        rule: Option<ValidationRule>,
+    },
+
+    #[error("Network error: {0}")]
+    NetworkError(String),
 
     #[error("YAML parsing error: {0}")]
     YamlError(#[from] serde_yaml::Error),
 }
 
-/// Convert JotError to String for Tauri command responses
-impl From<JotError> for String {
-    fn from(err: JotError) -> Self {
-        err.to_string()
+impl JotError {
+    /// Validation failure for a named input field
+    pub fn validation(field: &str, message: impl Into<String>) -> Self {
+        JotError::ValidationError {
+            field: field.to_string(),
+            message: message.into(),
+            rule: None,
+        }
+    }
+
+    /// Content rule violation for a named input field
+    pub fn rule_violation(field: &str, rule: ValidationRule, message: impl Into<String>) -> Self {
+        JotError::ValidationError {
+            field: field.to_string(),
+            message: message.into(),
+            rule: Some(rule),
+        }
     }
 }
@@ -1,7 +1,9 @@
+use crate::jots::frontmatter::FrontmatterSchema;
 use crate::jots::models::{Jot, JotError, JotFrontmatter};
 use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::BTreeMap;
 
 /// Lazy-compiled regex for tag extraction
 static TAG_REGEX: Lazy<Regex> =
@@ -11,6 +13,10 @@ static TAG_REGEX: Lazy<Regex> =
 static LINK_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\[\[([^\]]+)\]\]").expect("Invalid link regex"));
 
+/// Lazy-compiled regex for ![[embed]] extraction (alias and heading/block suffixes dropped)
+static EMBED_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"!\[\[([^\]|#]+)[^\]]*\]\]").expect("Invalid embed regex"));
+
 /// Extract #tags from content
 /// Input: "Meeting about #work and #project-x"
 /// Output: ["work", "project-x"]
@@ -31,23 +37,152 @@ pub fn extract_links(content: &str) -> Vec<String> {
         .collect()
 }
 
+/// Lazy-compiled regex for validating a single tag name
+static TAG_NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-zA-Z][a-zA-Z0-9_-]*$").expect("Invalid tag name regex"));
+
+/// Tag name without a leading '#', checked against the tag syntax
+pub fn normalize_tag(tag: &str) -> Result<String, JotError> {
+    let tag = tag.trim().trim_start_matches('#');
+    if !TAG_NAME_REGEX.is_match(tag) {
+        return Err(JotError::validation(
+            "tag",
+            format!("'{}' is not a valid tag", tag),
+        ));
+    }
+    Ok(tag.to_string())
+}
+
+/// Append #tag to content unless it already has it
+/// Input: "Meeting notes", "work"
+/// Output: "Meeting notes #work"
+pub fn add_tag(content: &str, tag: &str) -> String {
+    if extract_tags(content).iter().any(|t| t == tag) {
+        return content.to_string();
+    }
+
+    let content = content.trim_end();
+    if content.is_empty() {
+        format!("#{}", tag)
+    } else {
+        format!("{} #{}", content, tag)
+    }
+}
+
+/// Remove every #tag occurrence (and the space before it) from content
+/// Input: "Meeting #work notes", "work"
+/// Output: "Meeting notes"
+pub fn remove_tag(content: &str, tag: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+
+    for cap in TAG_REGEX.captures_iter(content) {
+        if &cap[1] != tag {
+            continue;
+        }
+        let whole = cap.get(0).expect("capture 0 always exists");
+        let mut start = whole.start();
+        if content[last..start].ends_with(' ') {
+            start -= 1;
+        }
+        result.push_str(&content[last..start]);
+        last = whole.end();
+    }
+    result.push_str(&content[last..]);
+
+    result.trim().to_string()
+}
+
+/// Lazy-compiled regex for `key:: value` fields on a line of their own (Dataview syntax)
+static LINE_FIELD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*(?:[-*][ \t]+)?([A-Za-z][\w-]*)::[ \t]+(\S.*)$")
+        .expect("Invalid line field regex")
+});
+
+/// Lazy-compiled regex for `[key:: value]` and `(key:: value)` fields within a line
+static BRACKET_FIELD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[\[(]([A-Za-z][\w-]*)::[ \t]+([^\])\n]+)[\])]")
+        .expect("Invalid bracket field regex")
+});
+
+/// Extract inline `key:: value` fields from content (first value of a key wins)
+/// Fields are a line of their own or wrapped in [ ] or ( ); a space must follow the "::"
+/// Input: "Standup\nmood:: tired\nSee (project:: Apollo)"
+/// Output: {"mood": "tired", "project": "Apollo"}
+pub fn extract_properties(content: &str) -> BTreeMap<String, serde_yaml::Value> {
+    let mut properties = BTreeMap::new();
+    let fields = LINE_FIELD_REGEX
+        .captures_iter(content)
+        .chain(BRACKET_FIELD_REGEX.captures_iter(content));
+
+    for cap in fields {
+        let value = cap[2].trim();
+        if !value.is_empty() {
+            properties
+                .entry(cap[1].to_string())
+                .or_insert_with(|| serde_yaml::Value::from(value));
+        }
+    }
+    properties
+}
+
+/// Drop repeated values, keeping first occurrences in order
+pub fn dedup(values: impl Iterator<Item = String>) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::new();
+    for value in values {
+        if !unique.contains(&value) {
+            unique.push(value);
+        }
+    }
+    unique
+}
+
+/// Extract ![[embedded files]] from content (each file once)
+/// Input: "Screenshot ![[Pasted image 1.png|300]]"
+/// Output: ["Pasted image 1.png"]
+pub fn extract_embeds(content: &str) -> Vec<String> {
+    dedup(
+        EMBED_REGEX
+            .captures_iter(content)
+            .map(|cap| cap[1].trim().to_string()),
+    )
+}
+
 /// Parse jot file into Jot struct
-/// Expects YAML frontmatter followed by content
-pub fn parse_jot_file(file_content: &str, file_path: &str) -> Result<Jot, JotError> {
+/// Expects YAML frontmatter (keys per `schema`) followed by content
+pub fn parse_jot_file(
+    file_content: &str,
+    file_path: &str,
+    schema: &FrontmatterSchema,
+) -> Result<Jot, JotError> {
     // Split frontmatter from content
     let parts: Vec<&str> = file_content.splitn(3, "---").collect();
 
     if parts.len() < 3 {
-        return Err(JotError::ParseError(
-            "Missing frontmatter delimiters".to_string(),
-        ));
+        return Err(JotError::ParseError {
+            file: file_path.to_string(),
+            message: "Missing frontmatter delimiters".to_string(),
+        });
     }
 
     let frontmatter_str = parts[1].trim();
-    let content = parts[2].trim().to_string();
+    // Leading newlines only separate the frontmatter: indentation of the first line is content
+    let content = parts[2]
+        .trim_start_matches(['\r', '\n'])
+        .trim_end()
+        .to_string();
 
     // Parse frontmatter
-    let frontmatter: JotFrontmatter = serde_yaml::from_str(frontmatter_str)?;
+    let frontmatter = serde_yaml::from_str(frontmatter_str)
+        .and_then(|mapping| schema.parse_mapping(mapping, &content))
+        .map_err(|e| JotError::ParseError {
+            file: file_path.to_string(),
+            message: e.to_string(),
+        })?;
+
+    // Inline fields are part of the content, which wins over stale frontmatter copies
+    let mut properties = frontmatter.properties;
+    properties.extend(extract_properties(&content));
 
     Ok(Jot {
         id: frontmatter.id,
@@ -57,12 +192,17 @@ pub fn parse_jot_file(file_content: &str, file_path: &str) -> Result<Jot, JotErr
         tags: frontmatter.tags,
         links: frontmatter.links,
         promoted: frontmatter.promoted,
+        archived: frontmatter.archived,
+        external_links: frontmatter.external_links,
+        properties,
         file_path: file_path.to_string(),
     })
 }
 
-/// Serialize Jot to markdown with frontmatter
-pub fn serialize_jot(jot: &Jot) -> String {
+/// Serialize Jot to markdown with frontmatter (keys, dates and extra properties per `schema`)
+/// Inline `key:: value` fields stay in the content only
+pub fn serialize_jot(jot: &Jot, schema: &FrontmatterSchema) -> String {
+    let inline = extract_properties(&jot.content);
     let frontmatter = JotFrontmatter {
         id: jot.id.clone(),
         created: jot.created_at,
@@ -70,9 +210,17 @@ pub fn serialize_jot(jot: &Jot) -> String {
         tags: jot.tags.clone(),
         links: jot.links.clone(),
         promoted: jot.promoted,
+        archived: jot.archived,
+        external_links: jot.external_links.clone(),
+        properties: jot
+            .properties
+            .iter()
+            .filter(|(key, _)| !inline.contains_key(*key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
     };
 
-    let yaml = serde_yaml::to_string(&frontmatter).unwrap_or_default();
+    let yaml = serde_yaml::to_string(&schema.to_mapping(&frontmatter)).unwrap_or_default();
 
     format!("---\n{}---\n\n{}", yaml, jot.content)
 }
@@ -103,6 +251,21 @@ mod tests {
         assert!(tags.is_empty());
     }
 
+    #[test]
+    fn test_add_and_remove_tag() {
+        assert_eq!(add_tag("Meeting notes", "work"), "Meeting notes #work");
+        assert_eq!(
+            add_tag("Meeting #work notes", "work"),
+            "Meeting #work notes"
+        );
+        assert_eq!(
+            remove_tag("Meeting #work notes #workshop #work", "work"),
+            "Meeting notes #workshop"
+        );
+        assert_eq!(normalize_tag("#project-x").unwrap(), "project-x");
+        assert!(normalize_tag("#123").is_err());
+    }
+
     #[test]
     fn test_extract_links() {
         let content = "Check [[Project Notes]] and [[Meeting Log]]";
@@ -110,6 +273,15 @@ mod tests {
         assert_eq!(links, vec!["Project Notes", "Meeting Log"]);
     }
 
+    #[test]
+    fn test_extract_embeds() {
+        let content = "See ![[diagram.png]] and ![[Pasted image 1.png|300]], not [[Note]]";
+        assert_eq!(
+            extract_embeds(content),
+            vec!["diagram.png", "Pasted image 1.png"]
+        );
+    }
+
     #[test]
     fn test_extract_links_with_spaces() {
         let content = "See [[Note with Spaces]]";
@@ -139,7 +311,12 @@ promoted: false
 
 Test content"#;
 
-        let jot = parse_jot_file(content, ".scribel/jots/test.md").unwrap();
+        let jot = parse_jot_file(
+            content,
+            ".scribel/jots/test.md",
+            &FrontmatterSchema::default(),
+        )
+        .unwrap();
         assert_eq!(jot.id, "jot-2025-01-19-143256-a1b2");
         assert_eq!(jot.content, "Test content");
         assert_eq!(jot.tags, vec!["work"]);
@@ -157,19 +334,103 @@ Test content"#;
             tags: vec!["work".to_string()],
             links: vec!["Project X".to_string()],
             promoted: false,
+            archived: false,
+            external_links: vec![],
+            properties: BTreeMap::new(),
             file_path: ".scribel/jots/test.md".to_string(),
         };
 
-        let markdown = serialize_jot(&jot);
+        let markdown = serialize_jot(&jot, &FrontmatterSchema::default());
         assert!(markdown.starts_with("---\n"));
         assert!(markdown.contains("id: jot-"));
         assert!(markdown.contains("Test content"));
     }
 
+    #[test]
+    fn test_serialize_with_custom_schema() {
+        let mut schema = FrontmatterSchema::default();
+        schema.keys.created = "date".to_string();
+        schema.include_links = false;
+        schema
+            .extra_properties
+            .insert("type".to_string(), serde_yaml::Value::from("jot"));
+
+        let jot = Jot {
+            id: "jot-2025-01-19-143256-a1b2".to_string(),
+            content: "Call about [[Budget]] #work".to_string(),
+            created_at: "2025-01-19T14:32:56Z".parse::<DateTime<Utc>>().unwrap(),
+            modified_at: "2025-01-19T14:32:56Z".parse::<DateTime<Utc>>().unwrap(),
+            tags: vec!["work".to_string()],
+            links: vec!["Budget".to_string()],
+            promoted: false,
+            archived: false,
+            external_links: vec![],
+            properties: BTreeMap::new(),
+            file_path: ".scribel/jots/test.md".to_string(),
+        };
+
+        let markdown = serialize_jot(&jot, &schema);
+        assert!(markdown.contains("date: 2025-01-19T14:32:56Z\n"));
+        assert!(markdown.contains("type: jot\n"));
+        assert!(!markdown.contains("links:"));
+
+        let parsed = parse_jot_file(&markdown, &jot.file_path, &schema).unwrap();
+        assert_eq!(parsed.created_at, jot.created_at);
+        assert_eq!(parsed.links, vec!["Budget"]);
+        assert_eq!(parsed.content, jot.content);
+    }
+
+    #[test]
+    fn test_extract_properties() {
+        let content = "Standup\nmood:: tired\n- project:: Apollo\nSee [due:: 2025-02-01] (mood:: fine)\nstd::vector and empty::";
+        let properties = extract_properties(content);
+
+        let pairs: Vec<(&str, &str)> = properties
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str().unwrap()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("due", "2025-02-01"),
+                ("mood", "tired"),
+                ("project", "Apollo")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_properties_round_trip() {
+        let content = r#"---
+id: jot-2025-01-19-143256-a1b2
+created: 2025-01-19T14:32:56Z
+modified: 2025-01-19T14:32:56Z
+tags: []
+links: []
+promoted: false
+priority: 2
+mood: stale
+---
+
+Call back
+mood:: happy"#;
+
+        let schema = FrontmatterSchema::default();
+        let jot = parse_jot_file(content, ".scribel/jots/test.md", &schema).unwrap();
+        assert_eq!(jot.properties["priority"], serde_yaml::Value::from(2));
+        assert_eq!(jot.properties["mood"], serde_yaml::Value::from("happy"));
+
+        let markdown = serialize_jot(&jot, &schema);
+        assert!(markdown.contains("priority: 2\n"));
+        assert!(!markdown.contains("mood: "));
+        let reparsed = parse_jot_file(&markdown, &jot.file_path, &schema).unwrap();
+        assert_eq!(reparsed.properties, jot.properties);
+    }
+
     #[test]
     fn test_parse_invalid_frontmatter() {
         let content = "Invalid content without frontmatter";
-        let result = parse_jot_file(content, "test.md");
+        let result = parse_jot_file(content, "test.md", &FrontmatterSchema::default());
         assert!(result.is_err());
     }
 }
@@ -0,0 +1,646 @@
+use crate::jots::models::{JotError, Reminder, ReminderStatus};
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use rusqlite::{Connection, OptionalExtension, Result as SqlResult, Row};
+use std::ops::Range;
+
+/// Time of day of a reminder that only names a date
+const DEFAULT_HOUR: u32 = 9;
+
+/// Lazy-compiled regex for the phrases that make a line a reminder
+static TRIGGER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)⏰|\breminder\b:?|\bremind\b(?:\s+me\b)?(?:\s+to\b)?")
+        .expect("Invalid reminder trigger regex")
+});
+
+/// Lazy-compiled regex for a leading list marker or checkbox
+static LIST_MARKER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:(?:[-*+]|\d+[.)])\s+)?(?:\[.\]\s+)?").expect("Invalid list marker regex")
+});
+
+const MONTHS: &str = "january|february|march|april|may|june|july|august|september|october|\
+                      november|december|jan|feb|mar|apr|jun|jul|aug|sept|sep|oct|nov|dec";
+
+/// "2025-01-20"
+static ISO_DATE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:on\s+)?(\d{4})-(\d{2})-(\d{2})\b").expect("Invalid ISO date regex")
+});
+
+/// "Jan 20", "January 20th, 2026"
+static MONTH_DAY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(?i)\b(?:on\s+)?({})\.?\s+(\d{{1,2}})(?:st|nd|rd|th)?\b(?:,?\s+(\d{{4}})\b)?",
+        MONTHS
+    ))
+    .expect("Invalid month day regex")
+});
+
+/// "20 Jan", "20th of January 2026"
+static DAY_MONTH_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(?i)\b(?:on\s+)?(?:the\s+)?(\d{{1,2}})(?:st|nd|rd|th)?\s+(?:of\s+)?({})\b\.?(?:,?\s+(\d{{4}})\b)?",
+        MONTHS
+    ))
+    .expect("Invalid day month regex")
+});
+
+/// "today", "tonight", "tomorrow", "the day after tomorrow"
+static RELATIVE_DAY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:the\s+)?(day after tomorrow|tomorrow|today|tonight)\b")
+        .expect("Invalid relative day regex")
+});
+
+/// "friday", "on next monday"
+static WEEKDAY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:on\s+)?(?:next\s+|this\s+)?(monday|tuesday|wednesday|thursday|friday|saturday|sunday)\b",
+    )
+    .expect("Invalid weekday regex")
+});
+
+/// "next week" (Monday)
+static NEXT_WEEK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bnext\s+week\b").expect("Invalid next week regex"));
+
+/// "in 20 minutes", "in an hour", "in two weeks"
+static OFFSET_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bin\s+(a|an|one|two|three|\d+)\s+(minute|min|hour|hr|day|week)s?\b")
+        .expect("Invalid offset regex")
+});
+
+/// "3pm", "at 3:30 pm"
+static TIME_12H_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:at\s+)?(\d{1,2})(?::([0-5]\d))?\s*(am|pm)\b")
+        .expect("Invalid 12-hour time regex")
+});
+
+/// "15:00", "at 9:30"
+static TIME_24H_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:at\s+)?([01]?\d|2[0-3]):([0-5]\d)\b").expect("Invalid 24-hour time regex")
+});
+
+/// "at noon", "in the evening"
+static TIME_WORD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:at\s+|in\s+the\s+|this\s+)?(noon|midnight|morning|afternoon|evening)\b")
+        .expect("Invalid time word regex")
+});
+
+/// Reminder found in jot content, before it is stored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedReminder {
+    /// The line the reminder was found on, which identifies it across edits
+    pub source: String,
+    pub text: String,
+    pub due_at: DateTime<Utc>,
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    let month = match &name.to_lowercase()[..3] {
+        "jan" => 1,
+        "feb" => 2,
+        "mar" => 3,
+        "apr" => 4,
+        "may" => 5,
+        "jun" => 6,
+        "jul" => 7,
+        "aug" => 8,
+        "sep" => 9,
+        "oct" => 10,
+        "nov" => 11,
+        "dec" => 12,
+        _ => return None,
+    };
+    Some(month)
+}
+
+/// Date of a month and day, in the given year or else the next time that day comes around
+fn month_day(today: NaiveDate, month: u32, day: u32, year: Option<&str>) -> Option<NaiveDate> {
+    if let Some(year) = year {
+        return NaiveDate::from_ymd_opt(year.parse().ok()?, month, day);
+    }
+    let date = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if date < today {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    } else {
+        Some(date)
+    }
+}
+
+/// Next `weekday` after today (a week out if today is that day)
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    today + Duration::days(if ahead == 0 { 7 } else { i64::from(ahead) })
+}
+
+fn span(cap: &Captures) -> Range<usize> {
+    cap.get(0).expect("capture 0 always exists").range()
+}
+
+/// Date named in a line, with the time of day the phrase implies ("tonight")
+fn find_date(line: &str, today: NaiveDate) -> Option<(NaiveDate, Option<NaiveTime>, Range<usize>)> {
+    if let Some(cap) = ISO_DATE_REGEX.captures(line) {
+        let date = NaiveDate::from_ymd_opt(
+            cap[1].parse().ok()?,
+            cap[2].parse().ok()?,
+            cap[3].parse().ok()?,
+        );
+        if let Some(date) = date {
+            return Some((date, None, span(&cap)));
+        }
+    }
+
+    if let Some(cap) = MONTH_DAY_REGEX.captures(line) {
+        let date = month_day(
+            today,
+            month_number(&cap[1])?,
+            cap[2].parse().ok()?,
+            cap.get(3).map(|m| m.as_str()),
+        );
+        if let Some(date) = date {
+            return Some((date, None, span(&cap)));
+        }
+    }
+
+    if let Some(cap) = DAY_MONTH_REGEX.captures(line) {
+        let date = month_day(
+            today,
+            month_number(&cap[2])?,
+            cap[1].parse().ok()?,
+            cap.get(3).map(|m| m.as_str()),
+        );
+        if let Some(date) = date {
+            return Some((date, None, span(&cap)));
+        }
+    }
+
+    if let Some(cap) = RELATIVE_DAY_REGEX.captures(line) {
+        let (date, time) = match cap[1].to_lowercase().as_str() {
+            "tonight" => (today, NaiveTime::from_hms_opt(20, 0, 0)),
+            "tomorrow" => (today + Duration::days(1), None),
+            "today" => (today, None),
+            _ => (today + Duration::days(2), None),
+        };
+        return Some((date, time, span(&cap)));
+    }
+
+    if let Some(cap) = WEEKDAY_REGEX.captures(line) {
+        let weekday: Weekday = cap[1].parse().ok()?;
+        return Some((next_weekday(today, weekday), None, span(&cap)));
+    }
+
+    if let Some(cap) = NEXT_WEEK_REGEX.captures(line) {
+        return Some((next_weekday(today, Weekday::Mon), None, span(&cap)));
+    }
+
+    None
+}
+
+/// Time of day named in a line
+fn find_time(line: &str) -> Option<(NaiveTime, Range<usize>)> {
+    if let Some(cap) = TIME_12H_REGEX.captures(line) {
+        let hour: u32 = cap[1].parse().ok()?;
+        let minute: u32 = cap.get(2).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+        if (1..=12).contains(&hour) {
+            let pm = cap[3].eq_ignore_ascii_case("pm");
+            let hour = hour % 12 + if pm { 12 } else { 0 };
+            return Some((NaiveTime::from_hms_opt(hour, minute, 0)?, span(&cap)));
+        }
+    }
+
+    if let Some(cap) = TIME_24H_REGEX.captures(line) {
+        let time = NaiveTime::from_hms_opt(cap[1].parse().ok()?, cap[2].parse().ok()?, 0)?;
+        return Some((time, span(&cap)));
+    }
+
+    if let Some(cap) = TIME_WORD_REGEX.captures(line) {
+        let hour = match cap[1].to_lowercase().as_str() {
+            "midnight" => 0,
+            "morning" => 9,
+            "noon" => 12,
+            "afternoon" => 15,
+            _ => 18,
+        };
+        return Some((NaiveTime::from_hms_opt(hour, 0, 0)?, span(&cap)));
+    }
+
+    None
+}
+
+/// Offset from now named in a line ("in 2 hours")
+fn find_offset(line: &str) -> Option<(Duration, Range<usize>)> {
+    let cap = OFFSET_REGEX.captures(line)?;
+    let amount: i64 = match cap[1].to_lowercase().as_str() {
+        "a" | "an" | "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        number => number.parse().ok()?,
+    };
+    let offset = match cap[2].to_lowercase().as_str() {
+        "minute" | "min" => Duration::try_minutes(amount)?,
+        "hour" | "hr" => Duration::try_hours(amount)?,
+        "day" => Duration::try_days(amount)?,
+        _ => Duration::try_weeks(amount)?,
+    };
+    Some((offset, span(&cap)))
+}
+
+/// When a line says something is due, relative to `now` (local time), and where it says so
+/// A date without a time is due at 9:00; a time without a date is due the next time it comes
+fn parse_when(line: &str, now: NaiveDateTime) -> Option<(NaiveDateTime, Vec<Range<usize>>)> {
+    if let Some((offset, span)) = find_offset(line) {
+        return Some((now.checked_add_signed(offset)?, vec![span]));
+    }
+
+    let date = find_date(line, now.date());
+    let time = find_time(line);
+    let default_time = NaiveTime::from_hms_opt(DEFAULT_HOUR, 0, 0)?;
+
+    match (date, time) {
+        (Some((date, _, date_span)), Some((time, time_span))) => {
+            Some((date.and_time(time), vec![date_span, time_span]))
+        }
+        (Some((date, implied, span)), None) => {
+            Some((date.and_time(implied.unwrap_or(default_time)), vec![span]))
+        }
+        (None, Some((time, span))) => {
+            let mut due = now.date().and_time(time);
+            if due <= now {
+                due += Duration::days(1);
+            }
+            Some((due, vec![span]))
+        }
+        (None, None) => None,
+    }
+}
+
+/// `line` without the given byte ranges, whitespace collapsed and stray punctuation trimmed
+fn without_spans(line: &str, mut spans: Vec<Range<usize>>) -> String {
+    spans.sort_by_key(|span| span.start);
+
+    let mut kept = String::with_capacity(line.len());
+    let mut last = 0;
+    for span in spans {
+        if span.start > last {
+            kept.push_str(&line[last..span.start]);
+        }
+        last = last.max(span.end);
+    }
+    kept.push_str(&line[last.min(line.len())..]);
+
+    kept.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c.is_whitespace() || ",.;:-".contains(c))
+        .to_string()
+}
+
+/// Extract reminders from content: lines with "remind me", "reminder" or ⏰ and a date or time
+/// Relative dates ("tomorrow at 3pm") are resolved against `written_at`, in local time
+/// Input: "remind me to review PR tomorrow at 3pm"
+/// Output: [review PR, due tomorrow 15:00]
+pub fn extract_reminders(content: &str, written_at: DateTime<Utc>) -> Vec<ParsedReminder> {
+    let now = written_at.with_timezone(&Local).naive_local();
+    let mut reminders = Vec::new();
+
+    for line in content.lines() {
+        let triggers: Vec<Range<usize>> =
+            TRIGGER_REGEX.find_iter(line).map(|m| m.range()).collect();
+        if triggers.is_empty() {
+            continue;
+        }
+        let Some((due, mut spans)) = parse_when(line, now) else {
+            continue;
+        };
+        let Some(due_at) = Local.from_local_datetime(&due).earliest() else {
+            continue;
+        };
+
+        spans.extend(triggers);
+        let marker = LIST_MARKER_REGEX.find(line).map_or(0, |m| m.end());
+        spans.push(0..marker);
+        let text = without_spans(line, spans);
+
+        reminders.push(ParsedReminder {
+            source: line.trim().to_string(),
+            text: if text.is_empty() {
+                line[marker..].trim().to_string()
+            } else {
+                text
+            },
+            due_at: due_at.with_timezone(&Utc),
+        });
+    }
+
+    reminders
+}
+
+fn status_to_str(status: ReminderStatus) -> &'static str {
+    match status {
+        ReminderStatus::Pending => "pending",
+        ReminderStatus::Fired => "fired",
+        ReminderStatus::Dismissed => "dismissed",
+    }
+}
+
+fn status_from_str(status: &str) -> ReminderStatus {
+    match status {
+        "fired" => ReminderStatus::Fired,
+        "dismissed" => ReminderStatus::Dismissed,
+        _ => ReminderStatus::Pending,
+    }
+}
+
+/// Bring a jot's reminders in line with its content
+/// Reminders still in the content keep their state; new ones are resolved against `written_at`
+/// (when the jot was last written) and start dismissed if already past at `now`
+pub fn sync_reminders(
+    conn: &Connection,
+    jot_id: &str,
+    content: &str,
+    written_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> SqlResult<()> {
+    let parsed = extract_reminders(content, written_at);
+
+    let mut stmt = conn.prepare_cached("SELECT id, source FROM reminders WHERE jot_id = ?1")?;
+    let existing = stmt
+        .query_map(rusqlite::params![jot_id], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<SqlResult<Vec<(i64, String)>>>()?;
+
+    for (id, source) in &existing {
+        if !parsed.iter().any(|reminder| &reminder.source == source) {
+            conn.execute("DELETE FROM reminders WHERE id = ?1", rusqlite::params![id])?;
+        }
+    }
+
+    for reminder in parsed {
+        let status = if reminder.due_at <= now {
+            ReminderStatus::Dismissed
+        } else {
+            ReminderStatus::Pending
+        };
+        conn.execute(
+            "INSERT OR IGNORE INTO reminders (jot_id, source, text, due_at, remind_at, status)
+             VALUES (?1, ?2, ?3, ?4, ?4, ?5)",
+            rusqlite::params![
+                jot_id,
+                reminder.source,
+                reminder.text,
+                reminder.due_at.timestamp_millis(),
+                status_to_str(status),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Map a reminders row to Reminder
+/// Expects columns: id, jot_id, text, due_at, remind_at, status
+fn row_to_reminder(row: &Row) -> SqlResult<Reminder> {
+    let due_at_ms: i64 = row.get(3)?;
+    let remind_at_ms: i64 = row.get(4)?;
+    let status: String = row.get(5)?;
+
+    Ok(Reminder {
+        id: row.get(0)?,
+        jot_id: row.get(1)?,
+        text: row.get(2)?,
+        due_at: DateTime::from_timestamp_millis(due_at_ms).unwrap_or_default(),
+        remind_at: DateTime::from_timestamp_millis(remind_at_ms).unwrap_or_default(),
+        status: status_from_str(&status),
+    })
+}
+
+/// Get a single reminder
+pub fn get_reminder(conn: &Connection, id: i64) -> Result<Option<Reminder>, JotError> {
+    let reminder = conn
+        .query_row(
+            "SELECT id, jot_id, text, due_at, remind_at, status FROM reminders WHERE id = ?1",
+            rusqlite::params![id],
+            row_to_reminder,
+        )
+        .optional()?;
+
+    Ok(reminder)
+}
+
+/// Reminders with the given status, or pending and fired ones when unset (soonest first)
+pub fn list_reminders(
+    conn: &Connection,
+    status: Option<ReminderStatus>,
+) -> Result<Vec<Reminder>, JotError> {
+    let statuses = match status {
+        Some(status) => vec![status_to_str(status)],
+        None => vec![
+            status_to_str(ReminderStatus::Pending),
+            status_to_str(ReminderStatus::Fired),
+        ],
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, jot_id, text, due_at, remind_at, status
+         FROM reminders
+         WHERE status IN ({})
+         ORDER BY remind_at, id",
+        vec!["?"; statuses.len()].join(", ")
+    ))?;
+
+    let reminders = stmt
+        .query_map(rusqlite::params_from_iter(statuses), row_to_reminder)?
+        .collect::<SqlResult<Vec<Reminder>>>()?;
+
+    Ok(reminders)
+}
+
+/// Set a reminder's status and the time it fires
+fn set_state(
+    conn: &Connection,
+    id: i64,
+    status: ReminderStatus,
+    remind_at: Option<DateTime<Utc>>,
+) -> Result<Reminder, JotError> {
+    let changed = conn.execute(
+        "UPDATE reminders SET status = ?2, remind_at = COALESCE(?3, remind_at) WHERE id = ?1",
+        rusqlite::params![
+            id,
+            status_to_str(status),
+            remind_at.map(|at| at.timestamp_millis()),
+        ],
+    )?;
+    if changed == 0 {
+        return Err(JotError::NotFound(format!("reminder {}", id)));
+    }
+
+    get_reminder(conn, id)?.ok_or_else(|| JotError::NotFound(format!("reminder {}", id)))
+}
+
+/// Fire the reminder again at `until`
+pub fn snooze(conn: &Connection, id: i64, until: DateTime<Utc>) -> Result<Reminder, JotError> {
+    set_state(conn, id, ReminderStatus::Pending, Some(until))
+}
+
+/// Stop a reminder from firing (again)
+pub fn dismiss(conn: &Connection, id: i64) -> Result<Reminder, JotError> {
+    set_state(conn, id, ReminderStatus::Dismissed, None)
+}
+
+/// Pending reminders due at `now`, marked fired (including ones missed while the app was closed)
+pub fn take_due(conn: &mut Connection, now: DateTime<Utc>) -> Result<Vec<Reminder>, JotError> {
+    let tx = conn.transaction()?;
+
+    let due = tx
+        .prepare(
+            "SELECT id, jot_id, text, due_at, remind_at, status
+             FROM reminders
+             WHERE status = 'pending' AND remind_at <= ?1
+             ORDER BY remind_at, id",
+        )?
+        .query_map(rusqlite::params![now.timestamp_millis()], row_to_reminder)?
+        .collect::<SqlResult<Vec<Reminder>>>()?;
+
+    let mut fired = Vec::with_capacity(due.len());
+    for reminder in due {
+        tx.execute(
+            "UPDATE reminders SET status = 'fired' WHERE id = ?1",
+            rusqlite::params![reminder.id],
+        )?;
+        fired.push(Reminder {
+            status: ReminderStatus::Fired,
+            ..reminder
+        });
+    }
+
+    tx.commit()?;
+    Ok(fired)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn local(text: &str) -> DateTime<Utc> {
+        Local
+            .from_local_datetime(&at(text))
+            .earliest()
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn due(line: &str, now: &str) -> Option<NaiveDateTime> {
+        parse_when(line, at(now)).map(|(due, _)| due)
+    }
+
+    #[test]
+    fn test_parse_relative_and_absolute_dates() {
+        // Wednesday
+        let now = "2025-01-15 10:00";
+
+        assert_eq!(due("tomorrow at 3pm", now), Some(at("2025-01-16 15:00")));
+        assert_eq!(due("tonight", now), Some(at("2025-01-15 20:00")));
+        assert_eq!(due("in 2 hours", now), Some(at("2025-01-15 12:00")));
+        assert_eq!(due("in a week", now), Some(at("2025-01-22 10:00")));
+        assert_eq!(due("on friday morning", now), Some(at("2025-01-17 09:00")));
+        assert_eq!(due("next wednesday", now), Some(at("2025-01-22 09:00")));
+        assert_eq!(due("next week", now), Some(at("2025-01-20 09:00")));
+        assert_eq!(due("Jan 20th at 9:30", now), Some(at("2025-01-20 09:30")));
+        assert_eq!(due("3 January", now), Some(at("2026-01-03 09:00")));
+        assert_eq!(
+            due("on 2025-02-01 at 12am", now),
+            Some(at("2025-02-01 00:00"))
+        );
+        assert_eq!(due("at 9am", now), Some(at("2025-01-16 09:00")));
+        assert_eq!(due("at noon", now), Some(at("2025-01-15 12:00")));
+        assert_eq!(due("some day", now), None);
+    }
+
+    #[test]
+    fn test_extract_reminders() {
+        let content = "Standup notes\n\
+            - [ ] remind me to review PR tomorrow at 3pm\n\
+            Reminder: call mom on Sunday\n\
+            Met Bob yesterday at 5pm\n\
+            ⏰ tomorrow";
+        let reminders = extract_reminders(content, local("2025-01-15 10:00"));
+
+        let found: Vec<(&str, DateTime<Utc>)> = reminders
+            .iter()
+            .map(|reminder| (reminder.text.as_str(), reminder.due_at))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("review PR", local("2025-01-16 15:00")),
+                ("call mom", local("2025-01-19 09:00")),
+                ("⏰ tomorrow", local("2025-01-16 09:00")),
+            ]
+        );
+        assert_eq!(
+            reminders[0].source,
+            "- [ ] remind me to review PR tomorrow at 3pm"
+        );
+    }
+
+    #[test]
+    fn test_sync_keeps_state_and_takes_due() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        let written = local("2025-01-15 10:00");
+        let content = "remind me to stretch in 1 hour\nremind me to pay rent tomorrow";
+        sync_reminders(&conn, "jot-a", content, written, written).unwrap();
+
+        let due = take_due(&mut conn, local("2025-01-15 11:30")).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].text, "stretch");
+        assert_eq!(due[0].status, ReminderStatus::Fired);
+        assert!(take_due(&mut conn, local("2025-01-15 11:30"))
+            .unwrap()
+            .is_empty());
+
+        // Re-syncing the same content later neither re-resolves nor revives reminders
+        let later = local("2025-01-15 12:00");
+        sync_reminders(&conn, "jot-a", content, later, later).unwrap();
+        let active = list_reminders(&conn, None).unwrap();
+        assert_eq!(active.len(), 2);
+        assert_eq!(active[0].status, ReminderStatus::Fired);
+        assert_eq!(active[1].due_at, local("2025-01-16 09:00"));
+
+        // Snoozed reminders fire again; dismissed ones are no longer active
+        let snoozed = snooze(&conn, due[0].id, local("2025-01-15 13:00")).unwrap();
+        assert_eq!(snoozed.status, ReminderStatus::Pending);
+        assert_eq!(
+            take_due(&mut conn, local("2025-01-15 13:00"))
+                .unwrap()
+                .len(),
+            1
+        );
+        dismiss(&conn, due[0].id).unwrap();
+        assert_eq!(list_reminders(&conn, None).unwrap().len(), 1);
+        assert!(matches!(dismiss(&conn, 999), Err(JotError::NotFound(_))));
+
+        // Removed from the content: gone; already past when first seen: dismissed
+        sync_reminders(
+            &conn,
+            "jot-a",
+            "remind me at 9am on 2025-01-01",
+            later,
+            later,
+        )
+        .unwrap();
+        assert!(list_reminders(&conn, None).unwrap().is_empty());
+        let dismissed = list_reminders(&conn, Some(ReminderStatus::Dismissed)).unwrap();
+        assert_eq!(dismissed.len(), 1);
+        assert_eq!(dismissed[0].text, "remind me at 9am on 2025-01-01");
+    }
+}
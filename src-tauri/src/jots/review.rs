@@ -0,0 +1,354 @@
+use crate::jots::index::row_to_jot;
+use crate::jots::models::{Jot, JotError, ReviewItem, ReviewState, ReviewStatus};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{Connection, OptionalExtension, Result as SqlResult, Row};
+use std::collections::HashMap;
+
+/// Spaced-repetition intervals: the nth "seen" schedules the jot this many days out
+pub const REVIEW_INTERVALS_DAYS: [u32; 7] = [1, 3, 7, 14, 30, 60, 120];
+
+/// New jots enter the queue once they are this old
+pub const MIN_AGE_HOURS: i64 = 12;
+
+/// Longest snooze accepted (about ten years)
+pub const MAX_SNOOZE_DAYS: u32 = 3650;
+
+fn status_to_str(status: ReviewStatus) -> &'static str {
+    match status {
+        ReviewStatus::Seen => "seen",
+        ReviewStatus::Snoozed => "snoozed",
+        ReviewStatus::Archived => "archived",
+        ReviewStatus::Promoted => "promoted",
+    }
+}
+
+fn status_from_str(status: &str) -> ReviewStatus {
+    match status {
+        "snoozed" => ReviewStatus::Snoozed,
+        "archived" => ReviewStatus::Archived,
+        "promoted" => ReviewStatus::Promoted,
+        _ => ReviewStatus::Seen,
+    }
+}
+
+/// Map jot_reviews columns, starting at `first`, to ReviewState
+/// Expects columns: jot_id, status, review_count, interval_days, last_reviewed_at, due_at
+fn row_to_review(row: &Row, first: usize) -> SqlResult<ReviewState> {
+    let status: String = row.get(first + 1)?;
+    let last_reviewed_at_ms: i64 = row.get(first + 4)?;
+    let due_at_ms: i64 = row.get(first + 5)?;
+
+    Ok(ReviewState {
+        jot_id: row.get(first)?,
+        status: status_from_str(&status),
+        review_count: row.get(first + 2)?,
+        interval_days: row.get(first + 3)?,
+        last_reviewed_at: DateTime::from_timestamp_millis(last_reviewed_at_ms).unwrap_or_default(),
+        due_at: DateTime::from_timestamp_millis(due_at_ms).unwrap_or_default(),
+    })
+}
+
+/// Get the review state of a jot, if it was ever reviewed
+pub fn get_review_state(conn: &Connection, jot_id: &str) -> Result<Option<ReviewState>, JotError> {
+    let state = conn
+        .query_row(
+            "SELECT jot_id, status, review_count, interval_days, last_reviewed_at, due_at
+             FROM jot_reviews
+             WHERE jot_id = ?1",
+            rusqlite::params![jot_id],
+            |row| row_to_review(row, 0),
+        )
+        .optional()?;
+
+    Ok(state)
+}
+
+/// When a jot snoozed for `snooze_days` (at least one) comes back
+fn snooze_until(now: DateTime<Utc>, snooze_days: u32) -> Result<DateTime<Utc>, JotError> {
+    if snooze_days > MAX_SNOOZE_DAYS {
+        return Err(JotError::validation(
+            "snooze_days",
+            format!("Snooze must be at most {} days", MAX_SNOOZE_DAYS),
+        ));
+    }
+
+    now.checked_add_signed(Duration::days(snooze_days.max(1) as i64))
+        .ok_or_else(|| JotError::validation("snooze_days", "Snooze ends too far in the future"))
+}
+
+/// Record the outcome of reviewing a jot and schedule its next appearance
+/// `snooze_days` only applies to ReviewStatus::Snoozed
+pub fn record_outcome(
+    conn: &Connection,
+    jot_id: &str,
+    outcome: ReviewStatus,
+    snooze_days: u32,
+    now: DateTime<Utc>,
+) -> Result<ReviewState, JotError> {
+    let previous = get_review_state(conn, jot_id)?;
+    let review_count = previous.as_ref().map_or(0, |state| state.review_count);
+    let interval_days = previous.as_ref().map_or(0, |state| state.interval_days);
+
+    let state = match outcome {
+        ReviewStatus::Seen => {
+            let step = (review_count as usize).min(REVIEW_INTERVALS_DAYS.len() - 1);
+            let interval_days = REVIEW_INTERVALS_DAYS[step];
+            ReviewState {
+                jot_id: jot_id.to_string(),
+                status: outcome,
+                review_count: review_count + 1,
+                interval_days,
+                last_reviewed_at: now,
+                due_at: now + Duration::days(interval_days as i64),
+            }
+        }
+        ReviewStatus::Snoozed => ReviewState {
+            jot_id: jot_id.to_string(),
+            status: outcome,
+            review_count,
+            interval_days,
+            last_reviewed_at: now,
+            due_at: snooze_until(now, snooze_days)?,
+        },
+        // Never due again: they drop out of the queue
+        ReviewStatus::Archived | ReviewStatus::Promoted => ReviewState {
+            jot_id: jot_id.to_string(),
+            status: outcome,
+            review_count,
+            interval_days,
+            last_reviewed_at: now,
+            due_at: now,
+        },
+    };
+
+    conn.execute(
+        "INSERT OR REPLACE INTO jot_reviews (jot_id, status, review_count, interval_days, last_reviewed_at, due_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![
+            state.jot_id,
+            status_to_str(state.status),
+            state.review_count,
+            state.interval_days,
+            state.last_reviewed_at.timestamp_millis(),
+            state.due_at.timestamp_millis(),
+        ],
+    )?;
+
+    Ok(state)
+}
+
+/// How often each tag appears on promoted jots
+fn promoted_tag_counts(conn: &Connection) -> Result<HashMap<String, u32>, JotError> {
+    let mut stmt = conn.prepare("SELECT tags FROM jot_index WHERE promoted = 1")?;
+    let tag_lists = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<SqlResult<Vec<String>>>()?;
+
+    let mut counts = HashMap::new();
+    for tags_json in tag_lists {
+        let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
+        for tag in tags {
+            *counts.entry(tag).or_insert(0) += 1;
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Queue priority of a candidate jot
+///
+/// - Age: days the jot has been waiting (since creation, or since it came due), capped at 30
+/// - Tags: jots sharing tags with promoted jots are more likely worth promoting
+/// - Prior outcomes: jots seen many times without action slowly sink
+fn score(
+    jot: &Jot,
+    review: Option<&ReviewState>,
+    promoted_tags: &HashMap<String, u32>,
+    now: DateTime<Utc>,
+) -> f64 {
+    let waiting_since = review.map_or(jot.created_at, |state| state.due_at);
+    let waiting_days = (now - waiting_since).num_hours().max(0) as f64 / 24.0;
+
+    let tag_affinity: u32 = jot
+        .tags
+        .iter()
+        .filter_map(|tag| promoted_tags.get(tag))
+        .sum();
+
+    let review_count = review.map_or(0, |state| state.review_count);
+
+    waiting_days.min(30.0) + (tag_affinity as f64).ln_1p() * 5.0 - review_count as f64
+}
+
+/// Unpromoted, unarchived jots due for review at `now`, highest priority first
+/// Never-reviewed jots qualify once MIN_AGE_HOURS old; reviewed ones once due again
+pub fn review_queue(
+    conn: &Connection,
+    limit: u32,
+    now: DateTime<Utc>,
+) -> Result<Vec<ReviewItem>, JotError> {
+    let min_created_at = (now - Duration::hours(MIN_AGE_HOURS)).timestamp_millis();
+
+    let mut stmt = conn.prepare(
+        "SELECT j.id, j.file_path, j.content, j.created_at, j.modified_at, j.tags, j.links, j.promoted,
+                j.archived, j.properties, r.jot_id, r.status, r.review_count, r.interval_days, r.last_reviewed_at, r.due_at
+         FROM jot_index j
+         LEFT JOIN jot_reviews r ON r.jot_id = j.id
+         WHERE j.promoted = 0 AND j.archived = 0
+           AND (
+               (r.jot_id IS NULL AND j.created_at <= ?1)
+               OR (r.status IN ('seen', 'snoozed') AND r.due_at <= ?2)
+           )",
+    )?;
+
+    let candidates = stmt
+        .query_map(
+            rusqlite::params![min_created_at, now.timestamp_millis()],
+            |row| {
+                let jot = row_to_jot(row)?;
+                let review = match row.get::<_, Option<String>>(10)? {
+                    Some(_) => Some(row_to_review(row, 10)?),
+                    None => None,
+                };
+                Ok((jot, review))
+            },
+        )?
+        .collect::<SqlResult<Vec<(Jot, Option<ReviewState>)>>>()?;
+
+    let promoted_tags = promoted_tag_counts(conn)?;
+
+    let mut queue: Vec<ReviewItem> = candidates
+        .into_iter()
+        .map(|(jot, review)| ReviewItem {
+            score: score(&jot, review.as_ref(), &promoted_tags, now),
+            jot,
+            review,
+        })
+        .collect();
+
+    queue.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.jot.created_at.cmp(&b.jot.created_at))
+    });
+    queue.truncate(limit as usize);
+
+    Ok(queue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::index::insert_jot;
+
+    fn create_test_jot(id: &str, age_days: i64, tags: &[&str], promoted: bool) -> Jot {
+        let created_at = Utc::now() - Duration::days(age_days);
+        Jot {
+            id: id.to_string(),
+            content: "Test".to_string(),
+            created_at,
+            modified_at: created_at,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            links: vec![],
+            promoted,
+            archived: false,
+            external_links: vec![],
+            properties: Default::default(),
+            file_path: format!(".scribel/jots/{}.md", id),
+        }
+    }
+
+    fn setup(jots: &[Jot]) -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+        for jot in jots {
+            insert_jot(&conn, jot).unwrap();
+        }
+        conn
+    }
+
+    fn queue_ids(conn: &Connection, now: DateTime<Utc>) -> Vec<String> {
+        review_queue(conn, 10, now)
+            .unwrap()
+            .into_iter()
+            .map(|item| item.jot.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_queue_picks_unpromoted_jots_old_enough() {
+        let conn = setup(&[
+            create_test_jot("jot-old", 5, &[], false),
+            create_test_jot("jot-fresh", 0, &[], false),
+            create_test_jot("jot-promoted", 5, &[], true),
+        ]);
+
+        assert_eq!(queue_ids(&conn, Utc::now()), vec!["jot-old"]);
+    }
+
+    #[test]
+    fn test_queue_prefers_older_and_promising_tags() {
+        let conn = setup(&[
+            create_test_jot("jot-2-days", 2, &[], false),
+            create_test_jot("jot-9-days", 9, &[], false),
+            create_test_jot("jot-tagged", 2, &["idea"], false),
+            create_test_jot("jot-done", 20, &["idea"], true),
+        ]);
+
+        assert_eq!(
+            queue_ids(&conn, Utc::now()),
+            vec!["jot-9-days", "jot-tagged", "jot-2-days"]
+        );
+    }
+
+    #[test]
+    fn test_seen_uses_spaced_intervals() {
+        let conn = setup(&[create_test_jot("jot-1", 5, &[], false)]);
+        let now = Utc::now();
+
+        let first = record_outcome(&conn, "jot-1", ReviewStatus::Seen, 1, now).unwrap();
+        assert_eq!(first.interval_days, 1);
+        assert!(queue_ids(&conn, now).is_empty());
+        assert_eq!(queue_ids(&conn, now + Duration::days(1)), vec!["jot-1"]);
+
+        let second = record_outcome(&conn, "jot-1", ReviewStatus::Seen, 1, now).unwrap();
+        assert_eq!(second.interval_days, 3);
+        assert_eq!(second.review_count, 2);
+        assert!(queue_ids(&conn, now + Duration::days(2)).is_empty());
+    }
+
+    #[test]
+    fn test_snooze_and_archive() {
+        let conn = setup(&[
+            create_test_jot("jot-1", 5, &[], false),
+            create_test_jot("jot-2", 5, &[], false),
+        ]);
+        let now = Utc::now();
+
+        let snoozed = record_outcome(&conn, "jot-1", ReviewStatus::Snoozed, 4, now).unwrap();
+        assert_eq!(snoozed.interval_days, 0);
+        record_outcome(&conn, "jot-2", ReviewStatus::Archived, 1, now).unwrap();
+
+        assert!(queue_ids(&conn, now + Duration::days(3)).is_empty());
+        assert_eq!(queue_ids(&conn, now + Duration::days(4)), vec!["jot-1"]);
+
+        let state = get_review_state(&conn, "jot-2").unwrap().unwrap();
+        assert_eq!(state.status, ReviewStatus::Archived);
+    }
+
+    #[test]
+    fn test_snooze_rejects_out_of_range_days() {
+        let conn = setup(&[create_test_jot("jot-1", 5, &[], false)]);
+        let now = Utc::now();
+
+        for days in [MAX_SNOOZE_DAYS + 1, u32::MAX] {
+            let result = record_outcome(&conn, "jot-1", ReviewStatus::Snoozed, days, now);
+            assert!(matches!(result, Err(JotError::ValidationError { .. })));
+        }
+        assert!(get_review_state(&conn, "jot-1").unwrap().is_none());
+
+        let snoozed =
+            record_outcome(&conn, "jot-1", ReviewStatus::Snoozed, MAX_SNOOZE_DAYS, now).unwrap();
+        assert_eq!(snoozed.due_at, now + Duration::days(MAX_SNOOZE_DAYS as i64));
+    }
+}
@@ -0,0 +1,492 @@
+use crate::jots::models::{
+    Granularity, JotError, JotStats, StatsQuery, TagFrequency, TagSeries, TimelineBucket,
+};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Local, Months, NaiveDate, Utc};
+use rusqlite::{Connection, Result as SqlResult};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Index fields statistics are derived from
+struct StatRow {
+    created_at: DateTime<Utc>,
+    promoted: bool,
+    tags: Vec<String>,
+    /// Best known promotion time (see load_rows)
+    promoted_at: DateTime<Utc>,
+}
+
+/// Most buckets a timeline or tag series may have (about ten years of days)
+const MAX_BUCKETS: usize = 3660;
+
+/// Time zone days, weeks and months are bucketed in
+#[derive(Debug, Clone, Copy)]
+enum Zone {
+    /// The system time zone, following daylight saving changes
+    Local,
+    Fixed(FixedOffset),
+}
+
+fn local_zone(query: &StatsQuery) -> Result<Zone, JotError> {
+    match query.utc_offset_minutes {
+        None => Ok(Zone::Local),
+        Some(minutes) => FixedOffset::east_opt(minutes.saturating_mul(60))
+            .map(Zone::Fixed)
+            .ok_or_else(|| {
+                JotError::validation("utc_offset_minutes", "Offset must be within 24 hours")
+            }),
+    }
+}
+
+fn local_date(at: DateTime<Utc>, zone: Zone) -> NaiveDate {
+    match zone {
+        Zone::Local => at.with_timezone(&Local).date_naive(),
+        Zone::Fixed(offset) => at.with_timezone(&offset).date_naive(),
+    }
+}
+
+/// First local day of the bucket containing `date`
+fn bucket_start(date: NaiveDate, granularity: Granularity) -> NaiveDate {
+    match granularity {
+        Granularity::Day => date,
+        Granularity::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        Granularity::Month => date.with_day(1).unwrap_or(date),
+    }
+}
+
+fn next_bucket(start: NaiveDate, granularity: Granularity) -> Option<NaiveDate> {
+    match granularity {
+        Granularity::Day => start.succ_opt(),
+        Granularity::Week => start.checked_add_signed(Duration::days(7)),
+        Granularity::Month => start.checked_add_months(Months::new(1)),
+    }
+}
+
+/// Every bucket start covering the query range (or the jots, where the range is open)
+/// Fails if that takes more than MAX_BUCKETS buckets
+fn bucket_starts(
+    query: &StatsQuery,
+    rows: &[StatRow],
+    zone: Zone,
+) -> Result<Vec<NaiveDate>, JotError> {
+    let first = query
+        .from
+        .or_else(|| rows.first().map(|row| row.created_at))
+        .map(|at| local_date(at, zone));
+    let last = query
+        .to
+        .map(|to| to - Duration::milliseconds(1))
+        .or_else(|| rows.last().map(|row| row.created_at))
+        .map(|at| local_date(at, zone));
+
+    let (Some(first), Some(last)) = (first, last) else {
+        return Ok(Vec::new());
+    };
+
+    let mut starts = Vec::new();
+    let mut current = Some(bucket_start(first, query.granularity));
+    while let Some(start) = current.filter(|start| *start <= last) {
+        if starts.len() == MAX_BUCKETS {
+            return Err(JotError::validation(
+                "granularity",
+                format!(
+                    "Range spans more than {} buckets; narrow it or use a coarser granularity",
+                    MAX_BUCKETS
+                ),
+            ));
+        }
+        starts.push(start);
+        current = next_bucket(start, query.granularity);
+    }
+
+    Ok(starts)
+}
+
+/// Load the jots created in the query range, oldest first
+///
+/// The index has no promotion timestamp, so it comes from history: the last revision
+/// recorded while the jot was still unpromoted is the moment it got promoted.
+/// Jots without such a revision fall back to modified_at.
+fn load_rows(conn: &Connection, query: &StatsQuery) -> Result<Vec<StatRow>, JotError> {
+    let from = query.from.map_or(i64::MIN, |at| at.timestamp_millis());
+    let to = query.to.map_or(i64::MAX, |at| at.timestamp_millis());
+
+    let mut stmt = conn.prepare(
+        "SELECT j.created_at, j.promoted, j.tags, COALESCE(p.promoted_at, j.modified_at)
+         FROM jot_index j
+         LEFT JOIN (
+             SELECT jot_id, MAX(recorded_at) AS promoted_at
+             FROM jot_revisions
+             WHERE promoted = 0
+             GROUP BY jot_id
+         ) p ON p.jot_id = j.id
+         WHERE j.created_at >= ?1 AND j.created_at < ?2
+         ORDER BY j.created_at ASC",
+    )?;
+
+    let rows = stmt
+        .query_map(rusqlite::params![from, to], |row| {
+            let created_at_ms: i64 = row.get(0)?;
+            let promoted: i32 = row.get(1)?;
+            let tags_json: String = row.get(2)?;
+            let promoted_at_ms: i64 = row.get(3)?;
+
+            Ok(StatRow {
+                created_at: DateTime::from_timestamp_millis(created_at_ms).unwrap_or_default(),
+                promoted: promoted != 0,
+                tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+                promoted_at: DateTime::from_timestamp_millis(promoted_at_ms).unwrap_or_default(),
+            })
+        })?
+        .collect::<SqlResult<Vec<StatRow>>>()?;
+
+    Ok(rows)
+}
+
+/// Jots created per day/week/month, including empty buckets
+pub fn timeline(conn: &Connection, query: &StatsQuery) -> Result<Vec<TimelineBucket>, JotError> {
+    let zone = local_zone(query)?;
+    let rows = load_rows(conn, query)?;
+
+    let mut counts: HashMap<NaiveDate, (u32, u32)> = HashMap::new();
+    for row in &rows {
+        let start = bucket_start(local_date(row.created_at, zone), query.granularity);
+        let entry = counts.entry(start).or_default();
+        entry.0 += 1;
+        if row.promoted {
+            entry.1 += 1;
+        }
+    }
+
+    Ok(bucket_starts(query, &rows, zone)?
+        .into_iter()
+        .map(|start| {
+            let (count, promoted) = counts.get(&start).copied().unwrap_or_default();
+            TimelineBucket {
+                start,
+                count,
+                promoted,
+            }
+        })
+        .collect())
+}
+
+/// Per-bucket usage of the `limit` most used tags
+pub fn tag_frequency(
+    conn: &Connection,
+    query: &StatsQuery,
+    limit: usize,
+) -> Result<TagFrequency, JotError> {
+    let zone = local_zone(query)?;
+    let rows = load_rows(conn, query)?;
+
+    let buckets = bucket_starts(query, &rows, zone)?;
+    let positions: HashMap<NaiveDate, usize> = buckets
+        .iter()
+        .enumerate()
+        .map(|(i, start)| (*start, i))
+        .collect();
+
+    let mut by_tag: BTreeMap<&str, Vec<u32>> = BTreeMap::new();
+    for row in &rows {
+        let start = bucket_start(local_date(row.created_at, zone), query.granularity);
+        let Some(&position) = positions.get(&start) else {
+            continue;
+        };
+
+        // A tag used twice in one jot still counts that jot once
+        let tags: BTreeSet<&str> = row.tags.iter().map(String::as_str).collect();
+        for tag in tags {
+            by_tag.entry(tag).or_insert_with(|| vec![0; buckets.len()])[position] += 1;
+        }
+    }
+
+    let mut series: Vec<TagSeries> = by_tag
+        .into_iter()
+        .map(|(tag, counts)| TagSeries {
+            tag: tag.to_string(),
+            total: counts.iter().sum(),
+            counts,
+        })
+        .collect();
+    // Stable sort keeps ties alphabetical
+    series.sort_by_key(|s| std::cmp::Reverse(s.total));
+    series.truncate(limit);
+
+    Ok(TagFrequency { buckets, series })
+}
+
+/// Lengths of (current, longest) runs of consecutive days in `days`
+/// The current run may end today or yesterday, so it isn't broken before today's first jot
+fn streaks(days: &BTreeSet<NaiveDate>, today: NaiveDate) -> (u32, u32) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+
+    for &day in days {
+        run = match previous {
+            Some(prev) if prev.succ_opt() == Some(day) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(day);
+    }
+
+    let yesterday = today.pred_opt();
+    let current = match previous {
+        Some(last) if last == today || Some(last) == yesterday => run,
+        _ => 0,
+    };
+
+    (current, longest)
+}
+
+/// Totals, promotion rate, average time to promotion and streaks
+/// `now` decides which day is "today" for the current streak
+pub fn summary(
+    conn: &Connection,
+    query: &StatsQuery,
+    now: DateTime<Utc>,
+) -> Result<JotStats, JotError> {
+    let zone = local_zone(query)?;
+    let rows = load_rows(conn, query)?;
+
+    let total_jots = rows.len() as u32;
+    let promoted: Vec<&StatRow> = rows.iter().filter(|row| row.promoted).collect();
+    let promoted_jots = promoted.len() as u32;
+
+    let promotion_rate = if total_jots == 0 {
+        0.0
+    } else {
+        promoted_jots as f64 / total_jots as f64
+    };
+
+    let average_hours_to_promotion = if promoted.is_empty() {
+        None
+    } else {
+        let total_ms: i64 = promoted
+            .iter()
+            .map(|row| (row.promoted_at - row.created_at).num_milliseconds().max(0))
+            .sum();
+        Some(total_ms as f64 / promoted.len() as f64 / 3_600_000.0)
+    };
+
+    let days: BTreeSet<NaiveDate> = rows
+        .iter()
+        .map(|row| local_date(row.created_at, zone))
+        .collect();
+    let (current_streak_days, longest_streak_days) = streaks(&days, local_date(now, zone));
+
+    Ok(JotStats {
+        total_jots,
+        promoted_jots,
+        promotion_rate,
+        average_hours_to_promotion,
+        current_streak_days,
+        longest_streak_days,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::history;
+    use crate::jots::index::insert_jot;
+    use crate::jots::models::Jot;
+    use chrono::TimeZone;
+
+    fn at(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn create_test_jot(id: &str, created_at: DateTime<Utc>, tags: &[&str]) -> Jot {
+        Jot {
+            id: id.to_string(),
+            content: "Test".to_string(),
+            created_at,
+            modified_at: created_at,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            links: vec![],
+            promoted: false,
+            archived: false,
+            external_links: vec![],
+            properties: Default::default(),
+            file_path: format!(".scribel/jots/{}.md", id),
+        }
+    }
+
+    /// Query bucketing in UTC, independent of the machine's time zone
+    fn utc() -> StatsQuery {
+        StatsQuery {
+            utc_offset_minutes: Some(0),
+            ..Default::default()
+        }
+    }
+
+    fn setup(jots: &[Jot]) -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+        for jot in jots {
+            insert_jot(&conn, jot).unwrap();
+        }
+        conn
+    }
+
+    #[test]
+    fn test_timeline_by_day_fills_gaps() {
+        let conn = setup(&[
+            create_test_jot("jot-1", at(2025, 3, 1, 10), &[]),
+            create_test_jot("jot-2", at(2025, 3, 1, 12), &[]),
+            create_test_jot("jot-3", at(2025, 3, 3, 9), &[]),
+        ]);
+
+        let buckets = timeline(&conn, &utc()).unwrap();
+        let counts: Vec<(NaiveDate, u32)> = buckets.iter().map(|b| (b.start, b.count)).collect();
+        assert_eq!(
+            counts,
+            vec![
+                (date(2025, 3, 1), 2),
+                (date(2025, 3, 2), 0),
+                (date(2025, 3, 3), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_timeline_uses_local_time() {
+        // 23:00 UTC on Mar 1 is already Mar 2 in UTC+2
+        let conn = setup(&[create_test_jot("jot-1", at(2025, 3, 1, 23), &[])]);
+
+        let query = StatsQuery {
+            utc_offset_minutes: Some(120),
+            ..Default::default()
+        };
+        let buckets = timeline(&conn, &query).unwrap();
+        assert_eq!(buckets[0].start, date(2025, 3, 2));
+    }
+
+    #[test]
+    fn test_timeline_defaults_to_system_time_zone() {
+        let created = at(2025, 3, 1, 23);
+        let conn = setup(&[create_test_jot("jot-1", created, &[])]);
+
+        let buckets = timeline(&conn, &StatsQuery::default()).unwrap();
+        assert_eq!(buckets[0].start, created.with_timezone(&Local).date_naive());
+    }
+
+    #[test]
+    fn test_timeline_rejects_too_many_buckets() {
+        let conn = setup(&[]);
+        let query = StatsQuery {
+            from: Some(at(2000, 1, 1, 0)),
+            to: Some(at(2025, 1, 1, 0)),
+            ..utc()
+        };
+        assert!(matches!(
+            timeline(&conn, &query),
+            Err(JotError::ValidationError { .. })
+        ));
+        assert!(tag_frequency(&conn, &query, 10).is_err());
+
+        // The same range by month is fine
+        let months = timeline(
+            &conn,
+            &StatsQuery {
+                granularity: Granularity::Month,
+                ..query
+            },
+        )
+        .unwrap();
+        assert_eq!(months.len(), 300);
+    }
+
+    #[test]
+    fn test_timeline_by_week_and_month() {
+        let conn = setup(&[
+            // Wednesday and the following Monday
+            create_test_jot("jot-1", at(2025, 3, 5, 10), &[]),
+            create_test_jot("jot-2", at(2025, 3, 10, 10), &[]),
+        ]);
+
+        let query = StatsQuery {
+            granularity: Granularity::Week,
+            ..utc()
+        };
+        let weeks = timeline(&conn, &query).unwrap();
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[0].start, date(2025, 3, 3));
+        assert_eq!(weeks[1].start, date(2025, 3, 10));
+
+        let query = StatsQuery {
+            granularity: Granularity::Month,
+            ..utc()
+        };
+        let months = timeline(&conn, &query).unwrap();
+        assert_eq!(months.len(), 1);
+        assert_eq!(months[0].start, date(2025, 3, 1));
+        assert_eq!(months[0].count, 2);
+    }
+
+    #[test]
+    fn test_tag_frequency() {
+        let conn = setup(&[
+            create_test_jot("jot-1", at(2025, 3, 1, 10), &["work", "idea"]),
+            create_test_jot("jot-2", at(2025, 3, 2, 10), &["work"]),
+            create_test_jot("jot-3", at(2025, 3, 2, 11), &["work", "work"]),
+        ]);
+
+        let frequency = tag_frequency(&conn, &utc(), 10).unwrap();
+        assert_eq!(frequency.buckets, vec![date(2025, 3, 1), date(2025, 3, 2)]);
+        assert_eq!(frequency.series[0].tag, "work");
+        assert_eq!(frequency.series[0].total, 3);
+        assert_eq!(frequency.series[0].counts, vec![1, 2]);
+        assert_eq!(frequency.series[1].tag, "idea");
+
+        let top = tag_frequency(&conn, &utc(), 1).unwrap();
+        assert_eq!(top.series.len(), 1);
+    }
+
+    #[test]
+    fn test_summary_promotion_and_streaks() {
+        let mut promoted = create_test_jot("jot-1", at(2025, 3, 1, 10), &[]);
+        let conn = setup(&[
+            promoted.clone(),
+            create_test_jot("jot-2", at(2025, 3, 2, 10), &[]),
+            create_test_jot("jot-3", at(2025, 3, 4, 10), &[]),
+            create_test_jot("jot-4", at(2025, 3, 5, 10), &[]),
+        ]);
+
+        // Promoted 6 hours after creation: the unpromoted state is recorded then
+        history::record_revision(&conn, &promoted).unwrap();
+        conn.execute(
+            "UPDATE jot_revisions SET recorded_at = ?1",
+            [at(2025, 3, 1, 16).timestamp_millis()],
+        )
+        .unwrap();
+        promoted.promoted = true;
+        crate::jots::index::update_jot_index(&conn, &promoted).unwrap();
+
+        let stats = summary(&conn, &utc(), at(2025, 3, 6, 8)).unwrap();
+        assert_eq!(stats.total_jots, 4);
+        assert_eq!(stats.promoted_jots, 1);
+        assert_eq!(stats.promotion_rate, 0.25);
+        assert_eq!(stats.average_hours_to_promotion, Some(6.0));
+        assert_eq!(stats.current_streak_days, 2);
+        assert_eq!(stats.longest_streak_days, 2);
+
+        let later = summary(&conn, &utc(), at(2025, 3, 9, 8)).unwrap();
+        assert_eq!(later.current_streak_days, 0);
+    }
+
+    #[test]
+    fn test_summary_empty() {
+        let conn = setup(&[]);
+        let stats = summary(&conn, &utc(), Utc::now()).unwrap();
+        assert_eq!(stats.total_jots, 0);
+        assert_eq!(stats.promotion_rate, 0.0);
+        assert_eq!(stats.average_hours_to_promotion, None);
+        assert!(timeline(&conn, &utc()).unwrap().is_empty());
+    }
+}
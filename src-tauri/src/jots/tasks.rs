@@ -0,0 +1,376 @@
+use crate::jots::models::{
+    ArchivedFilter, JotError, JotTask, TaskPriority, TaskQuery, TaskStatusFilter,
+};
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Result as SqlResult, Row};
+
+/// Lazy-compiled regex for a markdown checkbox list item ("- [ ] text", "1. [x] text")
+static TASK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[ \t]*(?:[-*+]|\d+[.)])[ \t]+\[([ xX])\][ \t]+(.*)$").expect("Invalid task regex")
+});
+
+/// Lazy-compiled regex for a due date: "📅 2025-01-20", "due: 2025-01-20", "[due:: 2025-01-20]"
+static DUE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[\[(]?(?:📅|\b[Dd]ue::?)[ \t]*(\d{4}-\d{2}-\d{2})[\])]?")
+        .expect("Invalid due date regex")
+});
+
+/// Lazy-compiled regex for a priority marker: Obsidian Tasks emojis or "priority: high"
+static PRIORITY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)🔺|⏫|🔼|🔽|⏬|[\[(]?\bpriority::?[ \t]*(highest|high|medium|low|lowest)\b[\])]?",
+    )
+    .expect("Invalid priority regex")
+});
+
+fn priority_from_marker(marker: &str) -> TaskPriority {
+    match marker.to_lowercase().as_str() {
+        "🔺" | "highest" => TaskPriority::Highest,
+        "⏫" | "high" => TaskPriority::High,
+        "🔼" | "medium" => TaskPriority::Medium,
+        "🔽" | "low" => TaskPriority::Low,
+        "⏬" | "lowest" => TaskPriority::Lowest,
+        _ => TaskPriority::Normal,
+    }
+}
+
+/// Stored as an integer so listings can sort by it
+fn priority_to_int(priority: TaskPriority) -> i64 {
+    match priority {
+        TaskPriority::Lowest => 0,
+        TaskPriority::Low => 1,
+        TaskPriority::Normal => 2,
+        TaskPriority::Medium => 3,
+        TaskPriority::High => 4,
+        TaskPriority::Highest => 5,
+    }
+}
+
+fn priority_from_int(priority: i64) -> TaskPriority {
+    match priority {
+        0 => TaskPriority::Lowest,
+        1 => TaskPriority::Low,
+        3 => TaskPriority::Medium,
+        4 => TaskPriority::High,
+        5 => TaskPriority::Highest,
+        _ => TaskPriority::Normal,
+    }
+}
+
+/// Content lines, as numbered by JotTask::line (a trailing '\r' is not part of the line)
+fn lines(content: &str) -> impl Iterator<Item = &str> {
+    content.split('\n').map(|line| line.trim_end_matches('\r'))
+}
+
+/// Whether a line opens or closes a fenced code block
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+/// Task on a single line, if the line is a checkbox item
+fn parse_task(jot_id: &str, line_number: u32, line: &str) -> Option<JotTask> {
+    let cap = TASK_REGEX.captures(line)?;
+    let body = &cap[2];
+
+    let due = DUE_REGEX
+        .captures(body)
+        .and_then(|due| NaiveDate::parse_from_str(&due[1], "%Y-%m-%d").ok());
+    let priority = PRIORITY_REGEX
+        .captures(body)
+        .map(|marker| {
+            let marker = marker
+                .get(1)
+                .unwrap_or_else(|| marker.get(0).expect("capture 0 always exists"));
+            priority_from_marker(marker.as_str())
+        })
+        .unwrap_or_default();
+
+    let text = DUE_REGEX.replace_all(body, "");
+    let text = PRIORITY_REGEX.replace_all(&text, "");
+
+    Some(JotTask {
+        jot_id: jot_id.to_string(),
+        line: line_number,
+        text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+        done: &cap[1] != " ",
+        due,
+        priority,
+    })
+}
+
+/// Extract checkbox tasks from content (tasks in fenced code blocks are skipped)
+/// Input: "Today\n- [ ] call Bob 📅 2025-01-20 ⏫\n- [x] email Ann"
+/// Output: [call Bob (line 1, due 2025-01-20, high), email Ann (line 2, done)]
+pub fn extract_tasks(jot_id: &str, content: &str) -> Vec<JotTask> {
+    let mut tasks = Vec::new();
+    let mut in_code = false;
+
+    for (number, line) in lines(content).enumerate() {
+        if is_fence(line) {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        if let Some(task) = parse_task(jot_id, number as u32, line) {
+            tasks.push(task);
+        }
+    }
+
+    tasks
+}
+
+/// Content with the checkbox of the task on `line` flipped, leaving the rest of the line as is
+/// None if that line no longer holds a task with this text (the jot changed meanwhile)
+pub fn toggle_line(content: &str, line: u32, text: &str) -> Option<String> {
+    let task = extract_tasks("", content)
+        .into_iter()
+        .find(|task| task.line == line)?;
+    if task.text != text {
+        return None;
+    }
+
+    let line_start: usize = content
+        .split('\n')
+        .take(line as usize)
+        .map(|line| line.len() + 1)
+        .sum();
+    let line_text = lines(&content[line_start..]).next()?;
+    let checkbox = TASK_REGEX.captures(line_text)?.get(1)?;
+
+    let start = line_start + checkbox.start();
+    let mark = if task.done { " " } else { "x" };
+    Some(format!(
+        "{}{}{}",
+        &content[..start],
+        mark,
+        &content[start + checkbox.len()..]
+    ))
+}
+
+/// Replace the jot_tasks rows of a jot with the tasks in its content
+pub fn sync_tasks(conn: &Connection, jot_id: &str, content: &str) -> SqlResult<()> {
+    conn.execute(
+        "DELETE FROM jot_tasks WHERE jot_id = ?1",
+        rusqlite::params![jot_id],
+    )?;
+
+    for task in extract_tasks(jot_id, content) {
+        conn.execute(
+            "INSERT OR REPLACE INTO jot_tasks (jot_id, line, text, done, due, priority)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                task.jot_id,
+                task.line,
+                task.text,
+                if task.done { 1 } else { 0 },
+                task.due.map(|due| due.format("%Y-%m-%d").to_string()),
+                priority_to_int(task.priority),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Map a jot_tasks row to JotTask
+/// Expects columns: jot_id, line, text, done, due, priority
+fn row_to_task(row: &Row) -> SqlResult<JotTask> {
+    let done: i32 = row.get(3)?;
+    let due: Option<String> = row.get(4)?;
+
+    Ok(JotTask {
+        jot_id: row.get(0)?,
+        line: row.get(1)?,
+        text: row.get(2)?,
+        done: done != 0,
+        due: due.and_then(|due| NaiveDate::parse_from_str(&due, "%Y-%m-%d").ok()),
+        priority: priority_from_int(row.get(5)?),
+    })
+}
+
+/// Tasks across jots: soonest due first (undated last), then highest priority, then oldest jot
+/// `today` is the user's local date, which overdue is judged against
+pub fn list_tasks(
+    conn: &Connection,
+    query: &TaskQuery,
+    today: NaiveDate,
+) -> Result<Vec<JotTask>, JotError> {
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+
+    match query.status {
+        TaskStatusFilter::Open => conditions.push("t.done = 0"),
+        TaskStatusFilter::Overdue => {
+            conditions.push("t.done = 0 AND t.due < ?");
+            values.push(Value::Text(today.format("%Y-%m-%d").to_string()));
+        }
+        TaskStatusFilter::Done => conditions.push("t.done = 1"),
+        TaskStatusFilter::All => {}
+    }
+
+    match query.archived {
+        ArchivedFilter::Exclude => conditions.push("j.archived = 0"),
+        ArchivedFilter::Only => conditions.push("j.archived = 1"),
+        ArchivedFilter::Include => {}
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT t.jot_id, t.line, t.text, t.done, t.due, t.priority
+         FROM jot_tasks t
+         JOIN jot_index j ON j.id = t.jot_id
+         {}
+         ORDER BY t.due IS NULL, t.due, t.priority DESC, j.created_at, t.line",
+        where_clause
+    ))?;
+
+    let tasks = stmt
+        .query_map(params_from_iter(values.iter()), row_to_task)?
+        .collect::<SqlResult<Vec<JotTask>>>()?;
+
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::index::insert_jot;
+    use crate::jots::models::Jot;
+    use chrono::{Duration, Utc};
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    fn create_test_jot(id: &str, content: &str, offset_secs: i64) -> Jot {
+        let timestamp = Utc::now() + Duration::seconds(offset_secs);
+        Jot {
+            id: id.to_string(),
+            content: content.to_string(),
+            created_at: timestamp,
+            modified_at: timestamp,
+            tags: vec![],
+            links: vec![],
+            promoted: false,
+            archived: false,
+            external_links: vec![],
+            properties: Default::default(),
+            file_path: format!(".scribel/jots/{}.md", id),
+        }
+    }
+
+    #[test]
+    fn test_extract_tasks() {
+        let content = "Today\r\n\
+            - [ ] call Bob 📅 2025-01-20 ⏫\n  \
+            * [x] email Ann\n\
+            1. [ ] file taxes [due:: 2025-04-15] priority: low\n\
+            ```\n- [ ] not a task\n```\n\
+            - [] nor this";
+        let tasks = extract_tasks("jot-a", content);
+
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].line, 1);
+        assert_eq!(tasks[0].text, "call Bob");
+        assert_eq!(tasks[0].due, Some(date("2025-01-20")));
+        assert_eq!(tasks[0].priority, TaskPriority::High);
+        assert!(!tasks[0].done);
+
+        assert_eq!(tasks[1].text, "email Ann");
+        assert!(tasks[1].done);
+        assert_eq!(tasks[1].priority, TaskPriority::Normal);
+
+        assert_eq!(tasks[2].text, "file taxes");
+        assert_eq!(tasks[2].due, Some(date("2025-04-15")));
+        assert_eq!(tasks[2].priority, TaskPriority::Low);
+    }
+
+    #[test]
+    fn test_toggle_line() {
+        let content = "Plan\r\n- [ ] call Bob 📅 2025-01-20\r\n- [x] email Ann";
+
+        let toggled = toggle_line(content, 1, "call Bob").unwrap();
+        assert_eq!(
+            toggled,
+            "Plan\r\n- [x] call Bob 📅 2025-01-20\r\n- [x] email Ann"
+        );
+        assert_eq!(
+            toggle_line(&toggled, 2, "email Ann").unwrap(),
+            "Plan\r\n- [x] call Bob 📅 2025-01-20\r\n- [ ] email Ann"
+        );
+
+        // The line changed since the task was listed
+        assert!(toggle_line(content, 1, "call Ann").is_none());
+        assert!(toggle_line(content, 0, "Plan").is_none());
+    }
+
+    #[test]
+    fn test_list_open_and_overdue_tasks() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        let first = create_test_jot(
+            "jot-1",
+            "- [ ] later 📅 2025-03-01\n- [ ] someday\n- [x] done 📅 2025-01-01",
+            0,
+        );
+        let second = create_test_jot(
+            "jot-2",
+            "- [ ] urgent 📅 2025-01-10 🔺\n- [ ] soon due: 2025-01-10",
+            1,
+        );
+        let mut archived = create_test_jot("jot-3", "- [ ] old 📅 2024-12-01", 2);
+        archived.archived = true;
+        for jot in [&first, &second, &archived] {
+            insert_jot(&conn, jot).unwrap();
+            sync_tasks(&conn, &jot.id, &jot.content).unwrap();
+        }
+
+        let today = date("2025-02-01");
+        let texts = |query: &TaskQuery| -> Vec<String> {
+            list_tasks(&conn, query, today)
+                .unwrap()
+                .into_iter()
+                .map(|task| task.text)
+                .collect()
+        };
+
+        assert_eq!(
+            texts(&TaskQuery::default()),
+            vec!["urgent", "soon", "later", "someday"]
+        );
+        assert_eq!(
+            texts(&TaskQuery {
+                status: TaskStatusFilter::Overdue,
+                ..Default::default()
+            }),
+            vec!["urgent", "soon"]
+        );
+        assert_eq!(
+            texts(&TaskQuery {
+                status: TaskStatusFilter::Overdue,
+                archived: ArchivedFilter::Include,
+                ..Default::default()
+            }),
+            vec!["old", "urgent", "soon"]
+        );
+        assert_eq!(
+            texts(&TaskQuery {
+                status: TaskStatusFilter::Done,
+                ..Default::default()
+            }),
+            vec!["done"]
+        );
+    }
+}
@@ -0,0 +1,378 @@
+use crate::jots::atomic;
+use crate::jots::models::{JotError, JotTemplate, TemplateValues};
+use crate::jots::parser::{add_tag, normalize_tag};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Template folder, relative to vault root
+pub const TEMPLATES_DIR: &str = ".scribel/templates";
+
+/// Default formats of {{date}} and {{time}}
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+const DEFAULT_TIME_FORMAT: &str = "%H:%M";
+
+/// Lazy-compiled regex for placeholders: {{date}}, {{date:%d %b}}, {{prompt:Attendees}}, ...
+static PLACEHOLDER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\{\s*(date|time|clipboard|cursor|prompt)\s*(?::([^}]*))?\}\}")
+        .expect("Invalid placeholder regex")
+});
+
+/// Optional frontmatter of a template file
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct TemplateFrontmatter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+/// Template content with placeholders filled in
+#[derive(Debug, PartialEq)]
+pub struct RenderedTemplate {
+    /// Content including the template's tags
+    pub content: String,
+
+    /// Byte offset of the {{cursor}} placeholder in `content`
+    pub cursor: Option<usize>,
+}
+
+/// Template name, checked to be usable as a file name
+fn validate_name(name: &str) -> Result<&str, JotError> {
+    let name = name.trim();
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(JotError::validation(
+            "name",
+            format!("'{}' is not a valid template name", name),
+        ));
+    }
+    Ok(name)
+}
+
+fn template_path(vault_path: &Path, name: &str) -> PathBuf {
+    vault_path.join(TEMPLATES_DIR).join(format!("{}.md", name))
+}
+
+/// Labels of {{prompt:Label}} placeholders, each once
+fn extract_prompts(content: &str) -> Vec<String> {
+    let mut prompts: Vec<String> = Vec::new();
+    for cap in PLACEHOLDER_REGEX.captures_iter(content) {
+        if &cap[1] != "prompt" {
+            continue;
+        }
+        let label = cap.get(2).map_or("", |m| m.as_str().trim());
+        if !label.is_empty() && !prompts.iter().any(|p| p == label) {
+            prompts.push(label.to_string());
+        }
+    }
+    prompts
+}
+
+/// Parse a template file: optional YAML frontmatter (description, tags) followed by the body
+fn parse_template(name: &str, file_content: &str) -> Result<JotTemplate, JotError> {
+    let (frontmatter, body) = match file_content.strip_prefix("---") {
+        Some(rest) => match rest.split_once("\n---") {
+            Some((yaml, body)) => {
+                let frontmatter: TemplateFrontmatter =
+                    serde_yaml::from_str(yaml).map_err(|e| JotError::ParseError {
+                        file: format!("{}/{}.md", TEMPLATES_DIR, name),
+                        message: e.to_string(),
+                    })?;
+                (frontmatter, body.trim_start_matches(['\r', '\n']))
+            }
+            None => (TemplateFrontmatter::default(), file_content),
+        },
+        None => (TemplateFrontmatter::default(), file_content),
+    };
+
+    let content = body.trim_end().to_string();
+    Ok(JotTemplate {
+        name: name.to_string(),
+        description: frontmatter.description,
+        tags: frontmatter
+            .tags
+            .iter()
+            .filter_map(|tag| normalize_tag(tag).ok())
+            .collect(),
+        prompts: extract_prompts(&content),
+        content,
+    })
+}
+
+/// Read a template by name
+pub fn read_template(vault_path: &Path, name: &str) -> Result<JotTemplate, JotError> {
+    let name = validate_name(name)?;
+    let path = template_path(vault_path, name);
+
+    if !path.exists() {
+        return Err(JotError::NotFound(format!("template {}", name)));
+    }
+
+    parse_template(name, &fs::read_to_string(&path)?)
+}
+
+/// List templates (by name)
+pub fn list_templates(vault_path: &Path) -> Result<Vec<JotTemplate>, JotError> {
+    let dir = vault_path.join(TEMPLATES_DIR);
+
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut templates = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+
+        if path.extension().and_then(|s| s.to_str()) != Some("md") {
+            continue;
+        }
+
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        match read_template(vault_path, name) {
+            Ok(template) => templates.push(template),
+            Err(e) => eprintln!("Failed to read template {}: {}", name, e),
+        }
+    }
+
+    templates.sort_by_key(|t| t.name.to_lowercase());
+    Ok(templates)
+}
+
+/// Create or replace a template file
+pub fn save_template(
+    vault_path: &Path,
+    name: &str,
+    description: Option<String>,
+    tags: &[String],
+    content: &str,
+) -> Result<JotTemplate, JotError> {
+    let name = validate_name(name)?;
+    let frontmatter = TemplateFrontmatter {
+        description: description.filter(|d| !d.trim().is_empty()),
+        tags: tags
+            .iter()
+            .map(|tag| normalize_tag(tag))
+            .collect::<Result<_, _>>()?,
+    };
+
+    let mut file_content = String::new();
+    if frontmatter.description.is_some() || !frontmatter.tags.is_empty() {
+        let yaml = serde_yaml::to_string(&frontmatter).map_err(|e| JotError::ParseError {
+            file: format!("{}/{}.md", TEMPLATES_DIR, name),
+            message: e.to_string(),
+        })?;
+        file_content.push_str(&format!("---\n{}---\n", yaml));
+    }
+    file_content.push_str(content.trim_end());
+    file_content.push('\n');
+
+    fs::create_dir_all(vault_path.join(TEMPLATES_DIR))?;
+    atomic::write_atomic(&template_path(vault_path, name), file_content.as_bytes())?;
+
+    parse_template(name, &file_content)
+}
+
+/// Delete a template file
+pub fn delete_template(vault_path: &Path, name: &str) -> Result<(), JotError> {
+    let name = validate_name(name)?;
+    let path = template_path(vault_path, name);
+
+    if !path.exists() {
+        return Err(JotError::NotFound(format!("template {}", name)));
+    }
+
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+/// Format `now` with a user-supplied strftime format
+fn format_time(now: DateTime<Local>, format: &str) -> Result<String, JotError> {
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return Err(JotError::validation(
+            "template",
+            format!("Invalid date format {:?}", format),
+        ));
+    }
+    Ok(now.format_with_items(items.into_iter()).to_string())
+}
+
+/// Fill in a template's placeholders and append its tags
+/// Input: "Meeting: {{prompt:Topic}} ({{date}})\n{{cursor}}", Topic = "Budget"
+/// Output: "Meeting: Budget (2025-01-19)\n", cursor after the newline
+pub fn render(
+    template: &JotTemplate,
+    values: &TemplateValues,
+    now: DateTime<Local>,
+) -> Result<RenderedTemplate, JotError> {
+    let mut content = String::with_capacity(template.content.len());
+    let mut cursor = None;
+    let mut last = 0;
+
+    for cap in PLACEHOLDER_REGEX.captures_iter(&template.content) {
+        let whole = cap.get(0).expect("capture 0 always exists");
+        content.push_str(&template.content[last..whole.start()]);
+        last = whole.end();
+
+        let argument = cap.get(2).map(|m| m.as_str().trim()).unwrap_or("");
+        match &cap[1] {
+            "date" | "time" => {
+                let default = if &cap[1] == "date" {
+                    DEFAULT_DATE_FORMAT
+                } else {
+                    DEFAULT_TIME_FORMAT
+                };
+                let format = if argument.is_empty() {
+                    default
+                } else {
+                    argument
+                };
+                content.push_str(&format_time(now, format)?);
+            }
+            "clipboard" => content.push_str(values.clipboard.as_deref().unwrap_or("")),
+            // Only the first {{cursor}} counts; others are dropped
+            "cursor" => {
+                cursor.get_or_insert(content.len());
+            }
+            _ => content.push_str(values.prompts.get(argument).map_or("", String::as_str)),
+        }
+    }
+    content.push_str(&template.content[last..]);
+
+    for tag in &template.tags {
+        content = add_tag(&content, tag);
+    }
+    if let Some(offset) = cursor {
+        // add_tag trims trailing whitespace
+        cursor = Some(offset.min(content.len()));
+    }
+
+    Ok(RenderedTemplate { content, cursor })
+}
+
+/// Offset in `saved` (UTF-16 code units) matching byte `offset` of `rendered`
+/// `saved` is `rendered` after validation, which only ever removes whitespace
+pub fn saved_cursor(rendered: &str, offset: usize, saved: &str) -> usize {
+    let mut saved_chars = saved.chars().peekable();
+    let mut position = 0;
+
+    for (index, c) in rendered.char_indices() {
+        if index >= offset {
+            break;
+        }
+        if saved_chars.peek() == Some(&c) {
+            saved_chars.next();
+            position += c.len_utf16();
+        }
+    }
+    position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::collections::BTreeMap;
+    use tempfile::tempdir;
+
+    fn now() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 1, 19, 14, 5, 0).unwrap()
+    }
+
+    #[test]
+    fn test_save_and_read_template() {
+        let vault = tempdir().unwrap();
+
+        let saved = save_template(
+            vault.path(),
+            "Meeting",
+            Some("Weekly sync".to_string()),
+            &["#meeting".to_string()],
+            "Meeting: {{prompt:Topic}}\n\
+             Attendees: {{ prompt: Attendees }}\n\
+             Topic again: {{prompt:Topic}}\n",
+        )
+        .unwrap();
+        assert_eq!(saved.tags, vec!["meeting"]);
+        assert_eq!(saved.prompts, vec!["Topic", "Attendees"]);
+
+        let read = read_template(vault.path(), "Meeting").unwrap();
+        assert_eq!(read, saved);
+        assert_eq!(list_templates(vault.path()).unwrap(), vec![saved]);
+
+        // Plain Markdown files without frontmatter are templates too
+        fs::write(
+            vault.path().join(TEMPLATES_DIR).join("Daily.md"),
+            "---\nnot frontmatter",
+        )
+        .unwrap();
+        let daily = read_template(vault.path(), "Daily").unwrap();
+        assert_eq!(daily.content, "---\nnot frontmatter");
+        assert!(daily.tags.is_empty());
+
+        delete_template(vault.path(), "Meeting").unwrap();
+        assert!(matches!(
+            read_template(vault.path(), "Meeting"),
+            Err(JotError::NotFound(_))
+        ));
+        assert!(matches!(
+            save_template(vault.path(), "../escape", None, &[], "x"),
+            Err(JotError::ValidationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let template = parse_template(
+            "Meeting",
+            "---\ntags: [meeting]\n---\n\
+             {{date}} {{time}} ({{date:%d %b}})\n\
+             Topic: {{prompt:Topic}}\n\
+             Attendees: {{prompt:Attendees}}\n\
+             {{cursor}}\n\
+             > {{clipboard}} {{unknown}}",
+        )
+        .unwrap();
+        let values = TemplateValues {
+            prompts: BTreeMap::from([("Topic".to_string(), "Budget".to_string())]),
+            clipboard: Some("pasted".to_string()),
+        };
+
+        let rendered = render(&template, &values, now()).unwrap();
+        assert_eq!(
+            rendered.content,
+            "2025-01-19 14:05 (19 Jan)\n\
+             Topic: Budget\n\
+             Attendees: \n\
+             \n\
+             > pasted {{unknown}} #meeting"
+        );
+        let cursor = rendered.cursor.unwrap();
+        assert!(rendered.content[cursor..].starts_with("\n> pasted"));
+
+        let bad = parse_template("Bad", "{{date:%Q}}").unwrap();
+        assert!(matches!(
+            render(&bad, &values, now()),
+            Err(JotError::ValidationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_saved_cursor_skips_trimmed_whitespace() {
+        let rendered = "\n  Café:  \n\nNotes";
+        let offset = rendered.find("Notes").unwrap();
+
+        assert_eq!(saved_cursor(rendered, offset, "Café:\n\nNotes"), 7);
+        assert_eq!(saved_cursor(rendered, 0, "Café:\n\nNotes"), 0);
+        assert_eq!(saved_cursor("😀 x", 5, "😀 x"), 3);
+    }
+}
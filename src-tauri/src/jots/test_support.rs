@@ -0,0 +1,10 @@
+use rusqlite::Connection;
+use tempfile::{tempdir, TempDir};
+
+/// Empty vault directory and an in-memory database with all migrations applied
+pub fn setup() -> (TempDir, Connection) {
+    let vault = tempdir().unwrap();
+    let conn = Connection::open_in_memory().unwrap();
+    crate::db::migrations::run_migrations(&conn).unwrap();
+    (vault, conn)
+}
@@ -0,0 +1,302 @@
+use crate::jots::atomic;
+use crate::jots::frontmatter::FrontmatterSchema;
+use crate::jots::models::{Jot, JotError, TrashedJot};
+use crate::jots::parser::parse_jot_file;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Trash folder, relative to vault root
+pub const TRASH_DIR: &str = ".scribel/trash";
+
+/// Deletion metadata stored next to each trashed jot as <id>.json
+#[derive(Debug, Serialize, Deserialize)]
+struct TrashMetadata {
+    id: String,
+    original_path: String,
+    deleted_at: DateTime<Utc>,
+}
+
+fn trashed_file_path(vault_path: &Path, id: &str) -> PathBuf {
+    vault_path.join(TRASH_DIR).join(format!("{}.md", id))
+}
+
+fn metadata_path(vault_path: &Path, id: &str) -> PathBuf {
+    vault_path.join(TRASH_DIR).join(format!("{}.json", id))
+}
+
+fn read_metadata(vault_path: &Path, id: &str) -> Result<TrashMetadata, JotError> {
+    let path = metadata_path(vault_path, id);
+
+    if !path.exists() {
+        return Err(JotError::NotFound(id.to_string()));
+    }
+
+    let json = fs::read_to_string(&path)?;
+    serde_json::from_str(&json).map_err(|e| JotError::ParseError {
+        file: format!("{}/{}.json", TRASH_DIR, id),
+        message: e.to_string(),
+    })
+}
+
+/// Whether a jot with this id is in the trash
+pub fn contains(vault_path: &Path, id: &str) -> bool {
+    trashed_file_path(vault_path, id).exists()
+}
+
+/// Move a jot file into .scribel/trash/ and record when it was deleted
+/// `original_path` is relative to the vault root
+pub fn move_to_trash(vault_path: &Path, id: &str, original_path: &str) -> Result<(), JotError> {
+    let source = vault_path.join(original_path);
+
+    if !source.exists() {
+        return Err(JotError::NotFound(id.to_string()));
+    }
+
+    fs::create_dir_all(vault_path.join(TRASH_DIR))?;
+
+    let metadata = TrashMetadata {
+        id: id.to_string(),
+        original_path: original_path.to_string(),
+        deleted_at: Utc::now(),
+    };
+    let json = serde_json::to_string_pretty(&metadata).map_err(|e| JotError::ParseError {
+        file: format!("{}/{}.json", TRASH_DIR, id),
+        message: e.to_string(),
+    })?;
+
+    // Metadata first, so a crash never leaves a trashed file we can't restore
+    atomic::write_atomic(&metadata_path(vault_path, id), json.as_bytes())?;
+    fs::rename(&source, trashed_file_path(vault_path, id))?;
+
+    Ok(())
+}
+
+/// List trashed jots (most recently deleted first)
+pub fn list_trash(
+    vault_path: &Path,
+    schema: &FrontmatterSchema,
+) -> Result<Vec<TrashedJot>, JotError> {
+    let trash_dir = vault_path.join(TRASH_DIR);
+
+    if !trash_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut trashed = Vec::new();
+    for entry in fs::read_dir(&trash_dir)? {
+        let path = entry?.path();
+
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        match read_trashed_jot(vault_path, id, schema) {
+            Ok(jot) => trashed.push(jot),
+            Err(e) => eprintln!("Failed to read trashed jot {}: {}", id, e),
+        }
+    }
+
+    trashed.sort_by_key(|t| std::cmp::Reverse(t.deleted_at));
+    Ok(trashed)
+}
+
+/// Read a single trashed jot with its deletion metadata
+pub fn read_trashed_jot(
+    vault_path: &Path,
+    id: &str,
+    schema: &FrontmatterSchema,
+) -> Result<TrashedJot, JotError> {
+    let metadata = read_metadata(vault_path, id)?;
+    let content = fs::read_to_string(trashed_file_path(vault_path, id))?;
+    let jot = parse_jot_file(&content, &metadata.original_path, schema)?;
+
+    Ok(TrashedJot {
+        jot,
+        deleted_at: metadata.deleted_at,
+    })
+}
+
+/// Move a trashed jot back to its original location
+/// Returns the restored jot so the caller can re-index it
+pub fn restore_jot(
+    vault_path: &Path,
+    id: &str,
+    schema: &FrontmatterSchema,
+) -> Result<Jot, JotError> {
+    let metadata = read_metadata(vault_path, id)?;
+    let target = vault_path.join(&metadata.original_path);
+
+    if target.exists() {
+        return Err(JotError::AlreadyExists(id.to_string()));
+    }
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::rename(trashed_file_path(vault_path, id), &target)?;
+    fs::remove_file(metadata_path(vault_path, id))?;
+
+    let content = fs::read_to_string(&target)?;
+    parse_jot_file(&content, &metadata.original_path, schema)
+}
+
+/// Permanently delete a trashed jot
+pub fn purge_jot(vault_path: &Path, id: &str) -> Result<(), JotError> {
+    let file_path = trashed_file_path(vault_path, id);
+    let meta_path = metadata_path(vault_path, id);
+
+    if !file_path.exists() && !meta_path.exists() {
+        return Err(JotError::NotFound(id.to_string()));
+    }
+
+    if file_path.exists() {
+        fs::remove_file(&file_path)?;
+    }
+    if meta_path.exists() {
+        fs::remove_file(&meta_path)?;
+    }
+
+    Ok(())
+}
+
+/// Permanently delete trashed jots older than the retention period
+/// Returns the number of jots purged
+pub fn purge_expired(
+    vault_path: &Path,
+    retention_days: u32,
+    schema: &FrontmatterSchema,
+) -> Result<u32, JotError> {
+    let cutoff = Utc::now() - Duration::days(i64::from(retention_days));
+
+    let mut count = 0;
+    for trashed in list_trash(vault_path, schema)? {
+        if trashed.deleted_at <= cutoff {
+            purge_jot(vault_path, &trashed.jot.id)?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::storage::{create_jot, delete_jot, read_jot, WriteOptions};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_delete_moves_jot_to_trash() {
+        let vault = tempdir().unwrap();
+
+        let jot = create_jot(vault.path(), "Trash me #work", &WriteOptions::default()).unwrap();
+        delete_jot(vault.path(), &jot).unwrap();
+
+        assert!(!vault.path().join(&jot.file_path).exists());
+        assert!(trashed_file_path(vault.path(), &jot.id).exists());
+
+        let trashed = list_trash(vault.path(), &FrontmatterSchema::default()).unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].jot.id, jot.id);
+        assert_eq!(trashed[0].jot.content, "Trash me #work");
+        assert_eq!(trashed[0].jot.file_path, jot.file_path);
+    }
+
+    #[test]
+    fn test_restore_jot() {
+        let vault = tempdir().unwrap();
+
+        let jot = create_jot(vault.path(), "Bring me back", &WriteOptions::default()).unwrap();
+        delete_jot(vault.path(), &jot).unwrap();
+
+        let restored = restore_jot(vault.path(), &jot.id, &FrontmatterSchema::default()).unwrap();
+        assert_eq!(restored.id, jot.id);
+        assert_eq!(restored.file_path, jot.file_path);
+
+        let fetched = read_jot(vault.path(), &jot.id, &FrontmatterSchema::default()).unwrap();
+        assert_eq!(fetched.content, "Bring me back");
+        assert!(list_trash(vault.path(), &FrontmatterSchema::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_restore_does_not_overwrite_existing_file() {
+        let vault = tempdir().unwrap();
+
+        let jot = create_jot(vault.path(), "Original", &WriteOptions::default()).unwrap();
+        delete_jot(vault.path(), &jot).unwrap();
+        fs::write(vault.path().join(&jot.file_path), "occupied").unwrap();
+
+        let result = restore_jot(vault.path(), &jot.id, &FrontmatterSchema::default());
+        assert!(matches!(result, Err(JotError::AlreadyExists(_))));
+        assert_eq!(
+            list_trash(vault.path(), &FrontmatterSchema::default())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_purge_jot() {
+        let vault = tempdir().unwrap();
+
+        let jot = create_jot(vault.path(), "Gone for good", &WriteOptions::default()).unwrap();
+        delete_jot(vault.path(), &jot).unwrap();
+
+        purge_jot(vault.path(), &jot.id).unwrap();
+
+        assert!(list_trash(vault.path(), &FrontmatterSchema::default())
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            restore_jot(vault.path(), &jot.id, &FrontmatterSchema::default()),
+            Err(JotError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_purge_expired() {
+        let vault = tempdir().unwrap();
+
+        let jot = create_jot(vault.path(), "Old news", &WriteOptions::default()).unwrap();
+        delete_jot(vault.path(), &jot).unwrap();
+
+        // Within retention period: kept
+        assert_eq!(
+            purge_expired(vault.path(), 30, &FrontmatterSchema::default()).unwrap(),
+            0
+        );
+        assert_eq!(
+            list_trash(vault.path(), &FrontmatterSchema::default())
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Zero-day retention: everything already deleted is expired
+        assert_eq!(
+            purge_expired(vault.path(), 0, &FrontmatterSchema::default()).unwrap(),
+            1
+        );
+        assert!(list_trash(vault.path(), &FrontmatterSchema::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_list_trash_empty() {
+        let vault = tempdir().unwrap();
+        assert!(list_trash(vault.path(), &FrontmatterSchema::default())
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,768 @@
+use crate::jots::atomic;
+use crate::jots::frontmatter::FrontmatterSchema;
+use crate::jots::models::{
+    ExternalLink, Jot, JotError, JotTemplate, ReviewState, ReviewStatus, TemplateJot,
+    TemplateValues, UpdateJotInput,
+};
+use crate::jots::storage::WriteOptions;
+use crate::jots::{
+    attachments, history, index, merge, review, storage, tasks, templates, trash, web_clip,
+};
+use chrono::{DateTime, Local, Utc};
+use rusqlite::{Connection, Transaction};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File change to undo if the index side of a unit of work fails
+enum Compensation {
+    /// A new file was written: remove it
+    RemoveFile(PathBuf),
+    /// A file was rewritten: put the previous bytes back
+    RestoreFile(PathBuf, Vec<u8>),
+    /// A jot was moved to the trash: move it back
+    RestoreFromTrash(String),
+    /// A jot was restored from the trash: trash it again
+    ReturnToTrash { id: String, file_path: String },
+    /// A file was moved: move it back
+    MoveFile { from: PathBuf, to: PathBuf },
+}
+
+/// Coordinates jot file changes with jot_index updates
+///
+/// Index writes go through one SQLite transaction; every file change registers a
+/// compensation. If any step fails (or the commit does), the transaction is rolled
+/// back and file changes are undone in reverse order, so files and index never drift.
+pub struct UnitOfWork<'a> {
+    tx: Transaction<'a>,
+    vault_path: &'a Path,
+    compensations: Vec<Compensation>,
+    /// Content rules and file name template for new and edited jots
+    options: WriteOptions,
+}
+
+/// Run `work` as a single unit of work: commit on success, roll back and compensate on error
+/// Content is validated against the default rules; files get the default names
+#[cfg(test)]
+pub fn run<T, F>(conn: &mut Connection, vault_path: &Path, work: F) -> Result<T, JotError>
+where
+    F: FnOnce(&mut UnitOfWork) -> Result<T, JotError>,
+{
+    run_with_options(conn, vault_path, WriteOptions::default(), work)
+}
+
+/// Run `work` as a single unit of work with the configured content rules and file names
+pub fn run_with_options<T, F>(
+    conn: &mut Connection,
+    vault_path: &Path,
+    options: WriteOptions,
+    work: F,
+) -> Result<T, JotError>
+where
+    F: FnOnce(&mut UnitOfWork) -> Result<T, JotError>,
+{
+    let mut uow = UnitOfWork {
+        tx: conn.transaction()?,
+        vault_path,
+        compensations: Vec::new(),
+        options,
+    };
+
+    let result = work(&mut uow);
+    let UnitOfWork {
+        tx,
+        compensations,
+        options,
+        ..
+    } = uow;
+
+    match result.and_then(|value| tx.commit().map(|_| value).map_err(JotError::from)) {
+        Ok(value) => Ok(value),
+        Err(e) => {
+            // Transaction (if not committed) rolls back when dropped
+            compensate(vault_path, &options.frontmatter, compensations);
+            Err(e)
+        }
+    }
+}
+
+/// Undo file changes, newest first
+fn compensate(vault_path: &Path, schema: &FrontmatterSchema, compensations: Vec<Compensation>) {
+    for compensation in compensations.into_iter().rev() {
+        let result = match compensation {
+            Compensation::RemoveFile(path) => fs::remove_file(&path).map_err(JotError::from),
+            Compensation::RestoreFile(path, bytes) => {
+                atomic::write_atomic(&path, &bytes).map_err(JotError::from)
+            }
+            Compensation::RestoreFromTrash(id) => {
+                trash::restore_jot(vault_path, &id, schema).map(|_| ())
+            }
+            Compensation::ReturnToTrash { id, file_path } => {
+                trash::move_to_trash(vault_path, &id, &file_path)
+            }
+            Compensation::MoveFile { from, to } => fs::rename(&to, &from).map_err(JotError::from),
+        };
+
+        if let Err(e) = result {
+            eprintln!("Failed to roll back file change: {}", e);
+        }
+    }
+}
+
+impl UnitOfWork<'_> {
+    /// Run one step so that its failure only undoes its own changes
+    /// Earlier and later steps still commit with the unit of work; the step's error is returned
+    pub fn isolated<T, F>(&mut self, step: F) -> Result<T, JotError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, JotError>,
+    {
+        self.tx.execute_batch("SAVEPOINT isolated_step")?;
+        let mark = self.compensations.len();
+
+        match step(self) {
+            Ok(value) => {
+                self.tx.execute_batch("RELEASE isolated_step")?;
+                Ok(value)
+            }
+            Err(e) => {
+                self.tx
+                    .execute_batch("ROLLBACK TO isolated_step; RELEASE isolated_step")?;
+                let undo = self.compensations.split_off(mark);
+                compensate(self.vault_path, &self.options.frontmatter, undo);
+                Err(e)
+            }
+        }
+    }
+
+    /// Read a jot, finding its file through the index
+    pub fn read_jot(&self, id: &str) -> Result<Jot, JotError> {
+        storage::find_jot(&self.tx, self.vault_path, id, &self.options.frontmatter)
+    }
+
+    /// Snapshot a jot's file so a rewrite can be undone
+    fn snapshot(&self, jot: &Jot) -> Result<Compensation, JotError> {
+        let path = self.vault_path.join(&jot.file_path);
+        let bytes = fs::read(&path)?;
+        Ok(Compensation::RestoreFile(path, bytes))
+    }
+
+    /// Create jot file and insert it into the index
+    pub fn create_jot(&mut self, content: &str) -> Result<Jot, JotError> {
+        let jot = storage::create_jot(self.vault_path, content, &self.options)?;
+        self.compensations.push(Compensation::RemoveFile(
+            self.vault_path.join(&jot.file_path),
+        ));
+
+        index::insert_jot(&self.tx, &jot)?;
+        Ok(jot)
+    }
+
+    /// Create a jot from a template (placeholders filled in, template tags added)
+    pub fn create_jot_from_template(
+        &mut self,
+        template: &JotTemplate,
+        values: &TemplateValues,
+    ) -> Result<TemplateJot, JotError> {
+        let rendered = templates::render(template, values, Local::now())?;
+        let jot = self.create_jot(&rendered.content)?;
+
+        // Saving trims whitespace, which can shift the cursor
+        let cursor = rendered
+            .cursor
+            .map(|offset| templates::saved_cursor(&rendered.content, offset, &jot.content));
+        Ok(TemplateJot { jot, cursor })
+    }
+
+    /// Create jot file with given timestamps and insert it into the index
+    pub fn create_jot_at(
+        &mut self,
+        content: &str,
+        created_at: DateTime<Utc>,
+        modified_at: DateTime<Utc>,
+    ) -> Result<Jot, JotError> {
+        let jot = storage::create_jot_at(
+            self.vault_path,
+            content,
+            created_at,
+            modified_at,
+            &self.options,
+        )?;
+        self.compensations.push(Compensation::RemoveFile(
+            self.vault_path.join(&jot.file_path),
+        ));
+
+        index::insert_jot(&self.tx, &jot)?;
+        Ok(jot)
+    }
+
+    /// Create a jot merged from others (see storage::create_merged_jot) and index it
+    pub fn create_merged_jot(
+        &mut self,
+        content: &str,
+        created_at: DateTime<Utc>,
+        modified_at: DateTime<Utc>,
+    ) -> Result<Jot, JotError> {
+        let jot = storage::create_merged_jot(
+            self.vault_path,
+            content,
+            created_at,
+            modified_at,
+            &self.options,
+        )?;
+        self.compensations.push(Compensation::RemoveFile(
+            self.vault_path.join(&jot.file_path),
+        ));
+
+        index::insert_jot(&self.tx, &jot)?;
+        Ok(jot)
+    }
+
+    /// Rewrite jot content, update the index and record the previous revision
+    /// Fails with JotError::Conflict if the jot changed since the client loaded it
+    pub fn update_jot(&mut self, input: &UpdateJotInput) -> Result<Jot, JotError> {
+        let previous = self.read_jot(&input.id)?;
+        merge::detect_conflict(&previous, input)?;
+        let snapshot = self.snapshot(&previous)?;
+
+        let jot = storage::update_jot(
+            self.vault_path,
+            previous.clone(),
+            &input.content,
+            &self.options,
+        )?;
+        self.record_rewrite(snapshot, &previous, jot)
+    }
+
+    /// Register the undo of a rewritten jot file, update the index and record the
+    /// previous revision
+    fn record_rewrite(
+        &mut self,
+        snapshot: Compensation,
+        previous: &Jot,
+        jot: Jot,
+    ) -> Result<Jot, JotError> {
+        self.compensations.push(snapshot);
+
+        index::update_jot_index(&self.tx, &jot)?;
+        history::record_revision(&self.tx, previous)?;
+        Ok(jot)
+    }
+
+    /// Store an attachment and embed it at the end of the jot as ![[file]]
+    pub fn add_attachment(
+        &mut self,
+        id: &str,
+        file_name: &str,
+        bytes: &[u8],
+    ) -> Result<Jot, JotError> {
+        let jot = self.read_jot(id)?;
+
+        let attachment =
+            attachments::write_attachment(&self.tx, self.vault_path, file_name, bytes)?;
+        self.compensations.push(Compensation::RemoveFile(
+            self.vault_path.join(&attachment.file_path),
+        ));
+        attachments::insert_attachment(&self.tx, &attachment)?;
+
+        let content = format!("{}\n\n![[{}]]", jot.content, attachment.file_name);
+        self.update_jot(&UpdateJotInput {
+            id: id.to_string(),
+            content,
+            base_modified_at: None,
+            base_content: None,
+        })
+    }
+
+    /// Store fetched web link metadata in the jot's frontmatter and the index
+    /// Not a revision: only captured metadata changes, the content stays as written
+    pub fn set_external_links(
+        &mut self,
+        id: &str,
+        fetched: Vec<ExternalLink>,
+    ) -> Result<Jot, JotError> {
+        let previous = self.read_jot(id)?;
+        let snapshot = self.snapshot(&previous)?;
+
+        let links = web_clip::merge_links(&previous, fetched);
+        let jot =
+            storage::set_external_links(self.vault_path, previous.clone(), links, &self.options)?;
+        self.compensations.push(snapshot);
+
+        index::update_jot_index(&self.tx, &jot)?;
+        Ok(jot)
+    }
+
+    /// Set promoted status, update the index and record the previous revision
+    pub fn set_promoted(&mut self, id: &str, promoted: bool) -> Result<Jot, JotError> {
+        let previous = self.read_jot(id)?;
+        let snapshot = self.snapshot(&previous)?;
+
+        let jot =
+            storage::set_promoted(self.vault_path, previous.clone(), promoted, &self.options)?;
+        self.compensations.push(snapshot);
+
+        index::update_jot_index(&self.tx, &jot)?;
+        history::record_revision(&self.tx, &previous)?;
+        Ok(jot)
+    }
+
+    /// Set archived status (optionally moving the file), update the index and record the previous revision
+    pub fn set_archived(
+        &mut self,
+        id: &str,
+        archived: bool,
+        move_file: bool,
+    ) -> Result<Jot, JotError> {
+        let previous = self.read_jot(id)?;
+        let snapshot = self.snapshot(&previous)?;
+
+        let jot = storage::set_archived(
+            self.vault_path,
+            previous.clone(),
+            archived,
+            move_file,
+            &self.options,
+        )?;
+        self.compensations.push(snapshot);
+        if jot.file_path != previous.file_path {
+            self.compensations.push(Compensation::RemoveFile(
+                self.vault_path.join(&jot.file_path),
+            ));
+        }
+
+        index::update_jot_index(&self.tx, &jot)?;
+        history::record_revision(&self.tx, &previous)?;
+        Ok(jot)
+    }
+
+    /// Move jot file into a folder under .scribel/jots/ and update the index
+    pub fn move_jot(&mut self, id: &str, folder: &str) -> Result<Jot, JotError> {
+        let previous = self.read_jot(id)?;
+
+        let jot = storage::move_jot(self.vault_path, previous.clone(), folder)?;
+        if jot.file_path != previous.file_path {
+            self.compensations.push(Compensation::MoveFile {
+                from: self.vault_path.join(&previous.file_path),
+                to: self.vault_path.join(&jot.file_path),
+            });
+        }
+
+        index::update_jot_index(&self.tx, &jot)?;
+        Ok(jot)
+    }
+
+    /// Rename jot file after the configured file name template and update the index
+    pub fn rename_jot(&mut self, id: &str) -> Result<Jot, JotError> {
+        let previous = self.read_jot(id)?;
+
+        let jot = storage::rename_jot(self.vault_path, previous.clone(), &self.options)?;
+        if jot.file_path != previous.file_path {
+            self.compensations.push(Compensation::MoveFile {
+                from: self.vault_path.join(&previous.file_path),
+                to: self.vault_path.join(&jot.file_path),
+            });
+        }
+
+        index::update_jot_index(&self.tx, &jot)?;
+        Ok(jot)
+    }
+
+    /// Move jot to the trash and remove it from the index
+    pub fn delete_jot(&mut self, id: &str) -> Result<(), JotError> {
+        let jot = self.read_jot(id)?;
+        storage::delete_jot(self.vault_path, &jot)?;
+        self.compensations
+            .push(Compensation::RestoreFromTrash(id.to_string()));
+
+        index::delete_jot_index(&self.tx, id)?;
+        Ok(())
+    }
+
+    /// Restore jot from the trash and re-insert it into the index
+    pub fn restore_jot(&mut self, id: &str) -> Result<Jot, JotError> {
+        let jot = trash::restore_jot(self.vault_path, id, &self.options.frontmatter)?;
+        self.compensations.push(Compensation::ReturnToTrash {
+            id: jot.id.clone(),
+            file_path: jot.file_path.clone(),
+        });
+
+        index::update_jot_index(&self.tx, &jot)?;
+        Ok(jot)
+    }
+
+    /// Copy a jot's file into `destination` (outside the vault) as <id>.md
+    /// Never overwrites: an existing file fails the export. The copy is removed on rollback
+    pub fn export_jot(&mut self, id: &str, destination: &Path) -> Result<PathBuf, JotError> {
+        let jot = self.read_jot(id)?;
+        fs::create_dir_all(destination)?;
+
+        let target = destination.join(format!("{}.md", jot.id));
+        let mut file = match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&target)
+        {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                return Err(JotError::validation(
+                    "destination",
+                    format!("{} already exists", target.display()),
+                ));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        self.compensations
+            .push(Compensation::RemoveFile(target.clone()));
+
+        let mut source = fs::File::open(self.vault_path.join(&jot.file_path))?;
+        io::copy(&mut source, &mut file)?;
+        Ok(target)
+    }
+
+    /// Check or uncheck the task on `line` of a jot's content, rewriting only that line
+    /// Fails with JotError::Conflict if the line no longer holds a task with this text.
+    /// The rest of the content is kept byte for byte: content rules and trimming don't apply
+    pub fn toggle_task(&mut self, id: &str, line: u32, text: &str) -> Result<Jot, JotError> {
+        let jot = self.read_jot(id)?;
+        let content = tasks::toggle_line(&jot.content, line, text)
+            .ok_or_else(|| JotError::Conflict(id.to_string()))?;
+        let snapshot = self.snapshot(&jot)?;
+
+        let toggled = storage::write_content(
+            self.vault_path,
+            jot.clone(),
+            content,
+            &self.options.frontmatter,
+        )?;
+        self.record_rewrite(snapshot, &jot, toggled)
+    }
+
+    /// Record a daily review outcome
+    /// Promoting during review also promotes the jot; archiving also archives it
+    pub fn record_review(
+        &mut self,
+        id: &str,
+        outcome: ReviewStatus,
+        snooze_days: u32,
+        archive_to_subfolder: bool,
+    ) -> Result<ReviewState, JotError> {
+        match outcome {
+            ReviewStatus::Promoted => {
+                self.set_promoted(id, true)?;
+            }
+            ReviewStatus::Archived => {
+                self.set_archived(id, true, archive_to_subfolder)?;
+            }
+            ReviewStatus::Seen | ReviewStatus::Snoozed => {
+                if index::get_jot(&self.tx, id)?.is_none() {
+                    return Err(JotError::NotFound(id.to_string()));
+                }
+            }
+        }
+
+        review::record_outcome(&self.tx, id, outcome, snooze_days, Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::test_support::setup;
+    use crate::jots::validation::ContentRules;
+
+    fn update_input(id: &str, content: &str) -> UpdateJotInput {
+        UpdateJotInput {
+            id: id.to_string(),
+            content: content.to_string(),
+            base_modified_at: None,
+            base_content: None,
+        }
+    }
+
+    #[test]
+    fn test_create_commits_file_and_index() {
+        let (vault, mut conn) = setup();
+
+        let jot = run(&mut conn, vault.path(), |uow| {
+            uow.create_jot("Hello #world")
+        })
+        .unwrap();
+
+        assert!(vault.path().join(&jot.file_path).exists());
+        let indexed = index::get_all_jots(&conn).unwrap();
+        assert_eq!(indexed.len(), 1);
+        assert_eq!(indexed[0].id, jot.id);
+    }
+
+    #[test]
+    fn test_create_removes_file_when_index_fails() {
+        let (vault, mut conn) = setup();
+        conn.execute("DROP TABLE jot_index", []).unwrap();
+
+        let result = run(&mut conn, vault.path(), |uow| uow.create_jot("Orphan"));
+        assert!(matches!(result, Err(JotError::DbError(_))));
+
+        let jots_dir = vault.path().join(".scribel/jots");
+        assert_eq!(fs::read_dir(&jots_dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_update_restores_file_when_index_fails() {
+        let (vault, mut conn) = setup();
+        let jot = run(&mut conn, vault.path(), |uow| uow.create_jot("Original")).unwrap();
+
+        // Revision recording fails after the file and index row were updated
+        conn.execute("DROP TABLE jot_revisions", []).unwrap();
+
+        let result = run(&mut conn, vault.path(), |uow| {
+            uow.update_jot(&update_input(&jot.id, "Changed"))
+        });
+        assert!(result.is_err());
+
+        let on_disk =
+            storage::read_jot(vault.path(), &jot.id, &FrontmatterSchema::default()).unwrap();
+        assert_eq!(on_disk.content, "Original");
+        let indexed = index::get_all_jots(&conn).unwrap();
+        assert_eq!(indexed[0].content, "Original");
+    }
+
+    #[test]
+    fn test_update_conflict_leaves_external_edit() {
+        let (vault, mut conn) = setup();
+        let jot = run(&mut conn, vault.path(), |uow| uow.create_jot("Original")).unwrap();
+
+        // Edited elsewhere after the client loaded it
+        storage::update_jot(
+            vault.path(),
+            jot.clone(),
+            "Edited in Obsidian",
+            &WriteOptions::default(),
+        )
+        .unwrap();
+
+        let input = UpdateJotInput {
+            base_modified_at: Some(jot.modified_at),
+            base_content: Some(jot.content.clone()),
+            ..update_input(&jot.id, "Edited in Scribel")
+        };
+        let result = run(&mut conn, vault.path(), |uow| uow.update_jot(&input));
+        assert!(matches!(result, Err(JotError::Conflict(_))));
+
+        let on_disk =
+            storage::read_jot(vault.path(), &jot.id, &FrontmatterSchema::default()).unwrap();
+        assert_eq!(on_disk.content, "Edited in Obsidian");
+    }
+
+    #[test]
+    fn test_set_promoted_records_revision() {
+        let (vault, mut conn) = setup();
+        let jot = run(&mut conn, vault.path(), |uow| uow.create_jot("Promote me")).unwrap();
+
+        let promoted = run(&mut conn, vault.path(), |uow| {
+            uow.set_promoted(&jot.id, true)
+        })
+        .unwrap();
+        assert!(promoted.promoted);
+
+        let revisions = history::list_revisions(&conn, &jot.id).unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert!(!revisions[0].promoted);
+    }
+
+    #[test]
+    fn test_delete_restores_file_when_index_fails() {
+        let (vault, mut conn) = setup();
+        let jot = run(&mut conn, vault.path(), |uow| uow.create_jot("Keep me")).unwrap();
+
+        conn.execute("DROP TABLE jot_index", []).unwrap();
+
+        let result = run(&mut conn, vault.path(), |uow| uow.delete_jot(&jot.id));
+        assert!(result.is_err());
+
+        assert!(vault.path().join(&jot.file_path).exists());
+        assert!(
+            trash::list_trash(vault.path(), &FrontmatterSchema::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_delete_and_restore() {
+        let (vault, mut conn) = setup();
+        let jot = run(&mut conn, vault.path(), |uow| uow.create_jot("Round trip")).unwrap();
+
+        run(&mut conn, vault.path(), |uow| uow.delete_jot(&jot.id)).unwrap();
+        assert!(index::get_all_jots(&conn).unwrap().is_empty());
+
+        run(&mut conn, vault.path(), |uow| uow.restore_jot(&jot.id)).unwrap();
+        let indexed = index::get_all_jots(&conn).unwrap();
+        assert_eq!(indexed.len(), 1);
+        assert_eq!(indexed[0].content, "Round trip");
+    }
+
+    #[test]
+    fn test_create_jot_from_template() {
+        let (vault, mut conn) = setup();
+        let template = templates::save_template(
+            vault.path(),
+            "Meeting",
+            None,
+            &["meeting".to_string()],
+            "\n  Meeting: {{prompt:Topic}}\nActions: {{cursor}}\n",
+        )
+        .unwrap();
+        let values = TemplateValues {
+            prompts: [("Topic".to_string(), "Budget".to_string())].into(),
+            clipboard: None,
+        };
+
+        let created = run(&mut conn, vault.path(), |uow| {
+            uow.create_jot_from_template(&template, &values)
+        })
+        .unwrap();
+        assert_eq!(created.jot.content, "Meeting: Budget\nActions: #meeting");
+        assert_eq!(created.jot.tags, vec!["meeting"]);
+        assert_eq!(created.cursor, Some("Meeting: Budget\nActions: ".len()));
+
+        let indexed = index::get_jot(&conn, &created.jot.id).unwrap().unwrap();
+        assert_eq!(indexed.content, created.jot.content);
+    }
+
+    #[test]
+    fn test_toggle_task_rewrites_line_and_index() {
+        let (vault, mut conn) = setup();
+        let jot = run(&mut conn, vault.path(), |uow| {
+            uow.create_jot("Errands\n- [ ] call Bob ⏫\n- [ ] buy milk")
+        })
+        .unwrap();
+
+        let toggled = run(&mut conn, vault.path(), |uow| {
+            uow.toggle_task(&jot.id, 2, "buy milk")
+        })
+        .unwrap();
+        assert_eq!(
+            toggled.content,
+            "Errands\n- [ ] call Bob ⏫\n- [x] buy milk"
+        );
+
+        let file = fs::read_to_string(vault.path().join(&jot.file_path)).unwrap();
+        assert!(file.ends_with("- [x] buy milk"));
+        let open = tasks::list_tasks(
+            &conn,
+            &Default::default(),
+            chrono::Local::now().date_naive(),
+        )
+        .unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].text, "call Bob");
+
+        // The listed task is gone from that line
+        let result = run(&mut conn, vault.path(), |uow| {
+            uow.toggle_task(&jot.id, 1, "buy milk")
+        });
+        assert!(matches!(result, Err(JotError::Conflict(_))));
+    }
+
+    #[test]
+    fn test_toggle_task_skips_content_rules() {
+        let (vault, mut conn) = setup();
+        let jot = run(&mut conn, vault.path(), |uow| {
+            uow.create_jot("Errands\n- [ ] buy milk")
+        })
+        .unwrap();
+
+        // Rules configured after the jot was written don't block ticking off its tasks
+        let options = WriteOptions {
+            rules: ContentRules {
+                forbidden_words: vec!["milk".to_string()],
+                ..ContentRules::default()
+            },
+            ..WriteOptions::default()
+        };
+        let toggled = run_with_options(&mut conn, vault.path(), options, |uow| {
+            uow.toggle_task(&jot.id, 1, "buy milk")
+        })
+        .unwrap();
+        assert_eq!(toggled.content, "Errands\n- [x] buy milk");
+    }
+
+    #[test]
+    fn test_review_promotion_promotes_jot() {
+        let (vault, mut conn) = setup();
+        let jot = run(&mut conn, vault.path(), |uow| {
+            uow.create_jot("Worth keeping")
+        })
+        .unwrap();
+
+        let state = run(&mut conn, vault.path(), |uow| {
+            uow.record_review(&jot.id, ReviewStatus::Promoted, 1, false)
+        })
+        .unwrap();
+        assert_eq!(state.status, ReviewStatus::Promoted);
+        assert!(
+            storage::read_jot(vault.path(), &jot.id, &FrontmatterSchema::default())
+                .unwrap()
+                .promoted
+        );
+
+        let result = run(&mut conn, vault.path(), |uow| {
+            uow.record_review("jot-missing", ReviewStatus::Seen, 1, false)
+        });
+        assert!(matches!(result, Err(JotError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_archive_move_rolls_back_when_index_fails() {
+        let (vault, mut conn) = setup();
+        let jot = run(&mut conn, vault.path(), |uow| {
+            uow.create_jot("Done with this")
+        })
+        .unwrap();
+
+        conn.execute("DROP TABLE jot_revisions", []).unwrap();
+
+        let result = run(&mut conn, vault.path(), |uow| {
+            uow.set_archived(&jot.id, true, true)
+        });
+        assert!(result.is_err());
+
+        // File is back in place, unarchived, and nothing is left in the archive folder
+        let fetched =
+            storage::read_jot(vault.path(), &jot.id, &FrontmatterSchema::default()).unwrap();
+        assert!(!fetched.archived);
+        assert_eq!(fetched.file_path, jot.file_path);
+        assert!(!vault
+            .path()
+            .join(storage::ARCHIVE_DIR)
+            .join(format!("{}.md", jot.id))
+            .exists());
+    }
+
+    #[test]
+    fn test_isolated_step_failure_keeps_other_steps() {
+        let (vault, mut conn) = setup();
+
+        let (kept, undone) = run(&mut conn, vault.path(), |uow| {
+            let kept = uow.create_jot("Kept")?;
+            let undone = uow
+                .isolated(|uow| {
+                    let jot = uow.create_jot("Undone")?;
+                    Err::<Jot, _>(JotError::NotFound(jot.id))
+                })
+                .unwrap_err();
+            Ok((kept, undone))
+        })
+        .unwrap();
+
+        let JotError::NotFound(undone_id) = undone else {
+            panic!("expected NotFound");
+        };
+        assert!(index::get_jot(&conn, &kept.id).unwrap().is_some());
+        assert!(index::get_jot(&conn, &undone_id).unwrap().is_none());
+        assert_eq!(
+            storage::list_jot_files(vault.path()).unwrap(),
+            vec![kept.file_path]
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use crate::jots::models::{JotError, ValidationRule};
+use crate::jots::parser::extract_tags;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// Default maximum jot length, in characters
+pub const DEFAULT_MAX_CHARS: usize = 10_000;
+
+/// Whitespace removed from jot content before it is saved
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrimPolicy {
+    /// Leading and trailing whitespace
+    #[default]
+    Both,
+    /// Leading blank lines and trailing whitespace; the first line keeps its indentation
+    KeepIndent,
+    /// Leading and trailing whitespace, and trailing whitespace on every line
+    Lines,
+}
+
+/// Apply a trim policy to content
+fn trim(content: &str, policy: TrimPolicy) -> String {
+    match policy {
+        TrimPolicy::Both => content.trim().to_string(),
+        TrimPolicy::KeepIndent => {
+            let content = content.trim_end();
+            let blank_prefix: usize = content
+                .split_inclusive('\n')
+                .take_while(|line| line.trim().is_empty())
+                .map(str::len)
+                .sum();
+            content[blank_prefix..].to_string()
+        }
+        TrimPolicy::Lines => content
+            .trim()
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// Rules jot content must satisfy on create and update (AppConfig `validation`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentRules {
+    /// Maximum length in characters (after trimming)
+    pub max_chars: usize,
+
+    /// Each pattern (a regex matched against whole tag names, without '#') must match a tag
+    pub required_tag_patterns: Vec<String>,
+
+    /// Words that may not appear in content (whole words, case-insensitive)
+    pub forbidden_words: Vec<String>,
+
+    pub trim: TrimPolicy,
+}
+
+impl Default for ContentRules {
+    fn default() -> Self {
+        Self {
+            max_chars: DEFAULT_MAX_CHARS,
+            required_tag_patterns: vec![],
+            forbidden_words: vec![],
+            trim: TrimPolicy::default(),
+        }
+    }
+}
+
+/// Compile a user-supplied pattern; `field` names the setting for the error
+fn compile(pattern: &str, field: &str) -> Result<Regex, JotError> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| JotError::validation(field, format!("Invalid pattern {:?}: {}", pattern, e)))
+}
+
+/// Check content against the rules; returns the content to save (trimmed per the policy)
+pub fn validate_content(content: &str, rules: &ContentRules) -> Result<String, JotError> {
+    let content = trim(content, rules.trim);
+
+    if content.trim().is_empty() {
+        return Err(JotError::rule_violation(
+            "content",
+            ValidationRule::NotEmpty,
+            "Content cannot be empty",
+        ));
+    }
+
+    if content.chars().count() > rules.max_chars {
+        return Err(JotError::rule_violation(
+            "content",
+            ValidationRule::MaxLength,
+            format!("Content exceeds {} characters", rules.max_chars),
+        ));
+    }
+
+    for word in &rules.forbidden_words {
+        let word = word.trim();
+        if word.is_empty() {
+            continue;
+        }
+        let pattern = format!(r"\b{}\b", regex::escape(word));
+        if compile(&pattern, "forbidden_words")?.is_match(&content) {
+            return Err(JotError::rule_violation(
+                "content",
+                ValidationRule::ForbiddenWord,
+                format!("Content contains forbidden word \"{}\"", word),
+            ));
+        }
+    }
+
+    if !rules.required_tag_patterns.is_empty() {
+        let tags = extract_tags(&content);
+        for pattern in &rules.required_tag_patterns {
+            let regex = compile(&format!("^(?:{})$", pattern), "required_tag_patterns")?;
+            if !tags.iter().any(|tag| regex.is_match(tag)) {
+                return Err(JotError::rule_violation(
+                    "tags",
+                    ValidationRule::RequiredTag,
+                    format!("Content needs a tag matching \"{}\"", pattern),
+                ));
+            }
+        }
+    }
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule_of(result: Result<String, JotError>) -> (String, ValidationRule) {
+        match result {
+            Err(JotError::ValidationError {
+                field,
+                rule: Some(rule),
+                ..
+            }) => (field, rule),
+            other => panic!("expected a rule violation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_max_length_counts_characters() {
+        let rules = ContentRules {
+            max_chars: 5,
+            ..ContentRules::default()
+        };
+
+        // 5 characters, 10 bytes
+        assert_eq!(validate_content("ééééé", &rules).unwrap(), "ééééé");
+        assert_eq!(
+            rule_of(validate_content("éééééé", &rules)),
+            ("content".to_string(), ValidationRule::MaxLength)
+        );
+        assert_eq!(
+            rule_of(validate_content("  \n ", &rules)),
+            ("content".to_string(), ValidationRule::NotEmpty)
+        );
+    }
+
+    #[test]
+    fn test_trim_policy() {
+        let content = "\n  - item  \n  - next \n\n";
+        let mut rules = ContentRules::default();
+        assert_eq!(
+            validate_content(content, &rules).unwrap(),
+            "- item  \n  - next"
+        );
+
+        rules.trim = TrimPolicy::KeepIndent;
+        assert_eq!(
+            validate_content(content, &rules).unwrap(),
+            "  - item  \n  - next"
+        );
+
+        rules.trim = TrimPolicy::Lines;
+        assert_eq!(
+            validate_content(content, &rules).unwrap(),
+            "- item\n  - next"
+        );
+    }
+
+    #[test]
+    fn test_forbidden_words_and_required_tags() {
+        let rules = ContentRules {
+            forbidden_words: vec!["secret".to_string()],
+            required_tag_patterns: vec!["project-.+".to_string()],
+            ..ContentRules::default()
+        };
+
+        assert!(validate_content("Plan the launch #project-atlas", &rules).is_ok());
+        // Whole words only
+        assert!(validate_content("Secretary call #project-atlas", &rules).is_ok());
+        assert_eq!(
+            rule_of(validate_content("The SECRET plan #project-atlas", &rules)),
+            ("content".to_string(), ValidationRule::ForbiddenWord)
+        );
+        assert_eq!(
+            rule_of(validate_content("Plan the launch #project", &rules)),
+            ("tags".to_string(), ValidationRule::RequiredTag)
+        );
+
+        let invalid = ContentRules {
+            required_tag_patterns: vec!["(".to_string()],
+            ..ContentRules::default()
+        };
+        assert!(matches!(
+            validate_content("Anything #tag", &invalid),
+            Err(JotError::ValidationError { rule: None, .. })
+        ));
+    }
+}
@@ -0,0 +1,414 @@
+use crate::jots::html;
+use crate::jots::models::{ExternalLink, Jot, JotError};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::io::Read;
+use std::time::Duration;
+
+/// How long a page fetch may take before giving up
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most of a page read when looking for metadata (2 MB)
+const MAX_PAGE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Longest excerpt kept, in characters
+const MAX_EXCERPT_CHARS: usize = 280;
+
+/// Shortest text block counted as prose when picking the excerpt
+const MIN_PARAGRAPH_CHARS: usize = 60;
+
+/// Lazy-compiled regex for http(s) URLs in jot content
+static URL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"https?://[^\s<>()\[\]{}"'`]+"#).expect("Invalid URL regex"));
+
+static TITLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").expect("Invalid title regex"));
+
+static META_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)<(meta|link)\s[^>]*>").expect("Invalid meta regex"));
+
+static ATTRIBUTE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"([a-zA-Z:_-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("Invalid attribute regex")
+});
+
+static BOILERPLATE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(?is)<(nav|header|footer|aside|form|title)[^>]*>",
+        r".*?</(nav|header|footer|aside|form|title)>"
+    ))
+    .expect("Invalid boilerplate regex")
+});
+
+/// http(s) URLs in jot content, in order of appearance, each once
+/// Trailing punctuation (end of a sentence, markdown emphasis) is not part of the URL
+pub fn extract_urls(content: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for m in URL_REGEX.find_iter(content) {
+        let url = m
+            .as_str()
+            .trim_end_matches(['.', ',', ';', ':', '!', '?', '*', '_', '~']);
+        if url.len() > "https://".len() && !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+    }
+    urls
+}
+
+/// URLs in the jot that have no captured metadata yet
+pub fn pending_urls(jot: &Jot) -> Vec<String> {
+    extract_urls(&jot.content)
+        .into_iter()
+        .filter(|url| !jot.external_links.iter().any(|link| &link.url == url))
+        .collect()
+}
+
+/// Combine a jot's captured links with newly fetched ones (fetched wins)
+/// Links whose URL is no longer in the content are dropped; order follows the content
+pub fn merge_links(jot: &Jot, fetched: Vec<ExternalLink>) -> Vec<ExternalLink> {
+    let mut links: Vec<ExternalLink> = jot
+        .external_links
+        .iter()
+        .filter(|link| !fetched.iter().any(|f| f.url == link.url))
+        .cloned()
+        .collect();
+    links.extend(fetched);
+    links.retain(|link| jot.content.contains(&link.url));
+    links.sort_by_key(|link| jot.content.find(&link.url));
+    links
+}
+
+/// Attributes of one <meta>/<link> tag, keys lowercased
+fn attributes(tag: &str) -> HashMap<String, String> {
+    ATTRIBUTE_REGEX
+        .captures_iter(tag)
+        .map(|cap| {
+            let value = cap.get(2).or_else(|| cap.get(3)).map_or("", |m| m.as_str());
+            (
+                cap[1].to_lowercase(),
+                html::decode_entities(value).trim().to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Resolve a root-relative URL ("/path") against the page URL
+fn absolute_url(page_url: &str, url: &str) -> String {
+    if !url.starts_with('/') || url.starts_with("//") {
+        return url.to_string();
+    }
+    let origin_end = page_url
+        .find("://")
+        .and_then(|scheme_end| {
+            page_url[scheme_end + 3..]
+                .find('/')
+                .map(|i| scheme_end + 3 + i)
+        })
+        .unwrap_or(page_url.len());
+    format!("{}{}", &page_url[..origin_end], url)
+}
+
+/// Inner HTML of the first <tag>...</tag>
+fn element<'a>(html: &'a str, tag: &str) -> Option<&'a str> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find(&format!("<{}", tag))?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = lower[start..]
+        .find(&format!("</{}", tag))
+        .map_or(html.len(), |i| start + i);
+    html.get(start..end)
+}
+
+/// Cut text to MAX_EXCERPT_CHARS on a word boundary, marking the cut with "…"
+fn truncate_excerpt(text: &str) -> String {
+    if text.chars().count() <= MAX_EXCERPT_CHARS {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(MAX_EXCERPT_CHARS).collect();
+    let cut = cut.rsplit_once(' ').map_or(cut.as_str(), |(head, _)| head);
+    format!("{}…", cut.trim_end_matches([',', ';', ':', '.', ' ']))
+}
+
+/// Readability-style excerpt: prose from the article (or main, or body), skipping
+/// navigation and other boilerplate
+fn excerpt(html: &str) -> Option<String> {
+    let content = element(html, "article")
+        .or_else(|| element(html, "main"))
+        .or_else(|| element(html, "body"))
+        .unwrap_or(html);
+    let content = BOILERPLATE_REGEX.replace_all(content, "");
+    let text = html::to_text(&content);
+
+    let blocks: Vec<String> = text
+        .split('\n')
+        .map(|block| block.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|block| !block.is_empty())
+        .collect();
+    let prose: Vec<&String> = blocks
+        .iter()
+        .filter(|block| block.chars().count() >= MIN_PARAGRAPH_CHARS)
+        .collect();
+
+    let excerpt = if prose.is_empty() {
+        blocks.join(" ")
+    } else {
+        prose
+            .iter()
+            .map(|block| block.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    (!excerpt.is_empty()).then(|| truncate_excerpt(&excerpt))
+}
+
+/// Page metadata from OpenGraph tags, falling back to <title>, meta description and the page text
+pub fn parse_metadata(url: &str, page: &str) -> ExternalLink {
+    let mut meta: HashMap<String, String> = HashMap::new();
+    let mut canonical: Option<String> = None;
+
+    for tag in META_REGEX.find_iter(page) {
+        let attrs = attributes(tag.as_str());
+        if tag.as_str()[1..].to_ascii_lowercase().starts_with("link") {
+            let is_canonical = attrs
+                .get("rel")
+                .is_some_and(|rel| rel.eq_ignore_ascii_case("canonical"));
+            if is_canonical && canonical.is_none() {
+                canonical = attrs.get("href").cloned();
+            }
+            continue;
+        }
+
+        let key = attrs.get("property").or_else(|| attrs.get("name"));
+        if let (Some(key), Some(value)) = (key, attrs.get("content")) {
+            if !value.is_empty() {
+                meta.entry(key.to_lowercase())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+    }
+
+    let title = meta.get("og:title").cloned().or_else(|| {
+        TITLE_REGEX
+            .captures(page)
+            .map(|cap| html::decode_entities(cap[1].trim()))
+            .filter(|title| !title.is_empty())
+    });
+    let description = meta
+        .get("og:description")
+        .or_else(|| meta.get("description"))
+        .cloned();
+    let canonical_url = canonical
+        .or_else(|| meta.get("og:url").cloned())
+        .filter(|canonical| !canonical.is_empty())
+        .map(|canonical| absolute_url(url, &canonical));
+
+    ExternalLink {
+        url: url.to_string(),
+        title,
+        description,
+        canonical_url,
+        site_name: meta.get("og:site_name").cloned(),
+        excerpt: excerpt(page),
+        fetched_at: Utc::now(),
+    }
+}
+
+/// Fetch a page and parse its metadata
+/// Non-HTML resources are recorded with the URL only; network failures are NetworkError
+pub fn fetch_metadata(url: &str) -> Result<ExternalLink, JotError> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(FETCH_TIMEOUT)
+        .user_agent("Scribel")
+        .build();
+    let response = agent
+        .get(url)
+        .set("Accept", "text/html,application/xhtml+xml")
+        .call()
+        .map_err(|e| JotError::NetworkError(e.to_string()))?;
+
+    let is_html = matches!(
+        response.content_type(),
+        "text/html" | "application/xhtml+xml"
+    );
+    if !is_html {
+        return Ok(ExternalLink {
+            url: url.to_string(),
+            title: None,
+            description: None,
+            canonical_url: None,
+            site_name: None,
+            excerpt: None,
+            fetched_at: Utc::now(),
+        });
+    }
+
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_PAGE_BYTES)
+        .read_to_end(&mut bytes)
+        .map_err(|e| JotError::NetworkError(e.to_string()))?;
+
+    Ok(parse_metadata(url, &String::from_utf8_lossy(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jots::frontmatter::FrontmatterSchema;
+    use crate::jots::{index, storage, unit_of_work};
+    use rusqlite::Connection;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::thread;
+    use tempfile::tempdir;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html><head>
+<title>Fallback title</title>
+<meta property="og:title" content="Rust &amp; Tauri">
+<meta property="og:site_name" content="Example Blog">
+<meta name="description" content="Building desktop apps">
+<link rel="canonical" href="/posts/rust-tauri">
+</head><body>
+<nav>Home | About | Archive</nav>
+<article><h1>Rust &amp; Tauri</h1>
+<p>Tauri lets you build small, fast desktop applications with a Rust backend and a web frontend.</p>
+</article>
+<footer>Copyright</footer>
+</body></html>"#;
+
+    /// Local HTTP stand-in answering one request with `body`
+    fn serve_once(content_type: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                content_type,
+                body.len(),
+                body
+            )
+            .unwrap();
+        });
+        format!("http://{}/posts/rust-tauri?ref=jot", address)
+    }
+
+    #[test]
+    fn test_extract_urls() {
+        let content = "See https://example.com/a. And [docs](https://tauri.app/start), \
+                       again https://example.com/a and **http://x.org/b?c=1**";
+        assert_eq!(
+            extract_urls(content),
+            vec![
+                "https://example.com/a",
+                "https://tauri.app/start",
+                "http://x.org/b?c=1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_metadata() {
+        let link = parse_metadata("https://blog.example.com/posts/rust-tauri?utm=x", PAGE);
+        assert_eq!(link.title.as_deref(), Some("Rust & Tauri"));
+        assert_eq!(link.description.as_deref(), Some("Building desktop apps"));
+        assert_eq!(
+            link.canonical_url.as_deref(),
+            Some("https://blog.example.com/posts/rust-tauri")
+        );
+        assert_eq!(link.site_name.as_deref(), Some("Example Blog"));
+        assert_eq!(
+            link.excerpt.as_deref(),
+            Some("Tauri lets you build small, fast desktop applications with a Rust backend and a web frontend.")
+        );
+
+        let bare = parse_metadata("https://x.org", "<title>Just a title</title>");
+        assert_eq!(bare.title.as_deref(), Some("Just a title"));
+        assert_eq!(bare.description, None);
+        assert_eq!(bare.excerpt, None);
+    }
+
+    #[test]
+    fn test_fetch_metadata_from_local_server() {
+        let url = serve_once("text/html; charset=utf-8", PAGE);
+        let link = fetch_metadata(&url).unwrap();
+        assert_eq!(link.url, url);
+        assert_eq!(link.title.as_deref(), Some("Rust & Tauri"));
+        assert!(link.canonical_url.unwrap().ends_with("/posts/rust-tauri"));
+
+        let url = serve_once("application/pdf", "%PDF-1.7");
+        let link = fetch_metadata(&url).unwrap();
+        assert_eq!(link.title, None);
+    }
+
+    #[test]
+    fn test_fetch_metadata_offline() {
+        let address = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let result = fetch_metadata(&format!("http://{}/", address));
+        assert!(matches!(result, Err(JotError::NetworkError(_))));
+    }
+
+    #[test]
+    fn test_captured_links_persist_and_follow_content() {
+        let vault = tempdir().unwrap();
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::run_migrations(&conn).unwrap();
+
+        let jot = unit_of_work::run(&mut conn, vault.path(), |uow| {
+            uow.create_jot("Read https://blog.example.com/post later")
+        })
+        .unwrap();
+        let urls = pending_urls(&jot);
+        assert_eq!(urls, vec!["https://blog.example.com/post"]);
+
+        let fetched = vec![parse_metadata(&urls[0], PAGE)];
+        let updated = unit_of_work::run(&mut conn, vault.path(), |uow| {
+            uow.set_external_links(&jot.id, fetched)
+        })
+        .unwrap();
+        assert_eq!(updated.modified_at, jot.modified_at);
+        assert!(pending_urls(&updated).is_empty());
+
+        let from_file =
+            storage::read_jot(vault.path(), &jot.id, &FrontmatterSchema::default()).unwrap();
+        assert_eq!(from_file.external_links, updated.external_links);
+        let from_index = index::get_jot(&conn, &jot.id).unwrap().unwrap();
+        assert_eq!(
+            from_index.external_links[0].title.as_deref(),
+            Some("Rust & Tauri")
+        );
+
+        // Removing the URL from the content drops its metadata
+        unit_of_work::run(&mut conn, vault.path(), |uow| {
+            uow.update_jot(&crate::jots::models::UpdateJotInput {
+                id: jot.id.clone(),
+                content: "Read it already".to_string(),
+                base_modified_at: None,
+                base_content: None,
+            })
+        })
+        .unwrap();
+        let rows: i64 = conn
+            .query_row("SELECT COUNT(*) FROM jot_links_external", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(rows, 0);
+    }
+}
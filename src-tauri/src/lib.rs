@@ -3,6 +3,7 @@ mod jots;
 mod commands;
 mod config;
 
+use std::path::PathBuf;
 use std::sync::Mutex;
 use tauri::Manager;
 
@@ -22,17 +23,96 @@ pub fn run() {
             std::fs::create_dir_all(&app_data)?;
             let db_path = app_data.join("scribel.db");
 
-            let conn = db::init_db(&db_path).map_err(|e| e.to_string())?;
-            db::run_migrations(&conn).map_err(|e| e.to_string())?;
+            // One writer plus a pool of WAL readers (runs migrations)
+            let database = db::Database::open(&db_path, db::pool::DEFAULT_READERS)
+                .map_err(|e| e.to_string())?;
 
-            app.manage(Mutex::new(conn));
+            app.manage(database.clone());
 
             // Vault path configuration (Phase 2 - Epic 2: Feature 2.1)
             // Currently uses default path, will be user-configurable in Epic 2
             let home = std::env::var("HOME").unwrap_or_else(|_| String::from("/tmp"));
             let default_vault = format!("{}/Documents/Obsidian", home);
+            let config = config::AppConfig::new(PathBuf::from(&default_vault));
             app.manage(Mutex::new(default_vault));
 
+            // Clean up temp files left by writes interrupted by a crash
+            let schema = config.frontmatter.clone();
+            if let Err(e) =
+                jots::storage::recover_interrupted_writes(&config.vault_path, &schema)
+            {
+                eprintln!("Failed to recover interrupted writes: {}", e);
+            }
+
+            // Notify the frontend of reminders as they come due
+            commands::reminders::spawn_reminder_scheduler(
+                app.handle().clone(),
+                database.clone(),
+                config.reminder_check_interval(),
+            );
+
+            // Periodically check jot files against the index and repair drift, and purge
+            // jots past the trash retention period (the first tick runs right away)
+            let handle = app.handle().clone();
+            let interval = config.consistency_check_interval();
+            let retention_days = config.trash_retention_days;
+            tauri::async_runtime::spawn(async move {
+                let mut ticker = tokio::time::interval(interval);
+                loop {
+                    ticker.tick().await;
+
+                    let vault = match handle.state::<Mutex<String>>().lock() {
+                        Ok(vault) => PathBuf::from(vault.as_str()),
+                        Err(_) => continue,
+                    };
+
+                    let check_vault = vault.clone();
+                    let check_schema = schema.clone();
+                    let result = database
+                        .write(move |conn| {
+                            jots::consistency::check_consistency(
+                                conn,
+                                &check_vault,
+                                true,
+                                &check_schema,
+                            )
+                        })
+                        .await;
+
+                    match result {
+                        Ok(report) if report.has_drift() => {
+                            eprintln!("Repaired jot index drift: {:?}", report);
+                        }
+                        Ok(_) => {}
+                        Err(e) => eprintln!("Jot consistency check failed: {}", e),
+                    }
+
+                    // File-only work: stays off the writer lock
+                    let purge_vault = vault.clone();
+                    let purge_schema = schema.clone();
+                    let purged = tokio::task::spawn_blocking(move || {
+                        jots::trash::purge_expired(&purge_vault, retention_days, &purge_schema)
+                    })
+                    .await;
+                    match purged {
+                        Ok(Ok(_)) => {}
+                        Ok(Err(e)) => eprintln!("Failed to purge expired trash: {}", e),
+                        Err(e) => eprintln!("Trash purge task failed: {}", e),
+                    }
+
+                    // Attachments orphaned by jots purged from the trash
+                    let result =
+                        commands::attachments::collect_garbage(&database, vault, schema.clone())
+                            .await;
+                    if let Err(e) = result {
+                        eprintln!("Attachment garbage collection failed: {}", e);
+                    }
+                }
+            });
+
+            // Settings read by commands
+            app.manage(config);
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -41,10 +121,44 @@ pub fn run() {
             commands::jots::get_jots,
             commands::jots::get_jot,
             commands::jots::update_jot,
+            commands::jots::merge_jot_update,
             commands::jots::delete_jot,
+            commands::jots::list_trash,
+            commands::jots::restore_jot,
+            commands::jots::purge_trashed_jot,
             commands::jots::search_jots,
             commands::jots::set_jot_promoted,
+            commands::jots::list_jot_revisions,
+            commands::jots::diff_jot_revisions,
+            commands::jots::restore_jot_revision,
             commands::jots::rebuild_jot_index,
+            commands::jots::rename_jot_files,
+            commands::jots::check_jot_consistency,
+            commands::jots::archive_jots,
+            commands::jots::unarchive_jots,
+            commands::jots::merge_jots,
+            commands::jots::split_jot,
+            commands::attachments::add_jot_attachment,
+            commands::attachments::list_jot_attachments,
+            commands::attachments::collect_attachment_garbage,
+            commands::bulk::bulk_jots,
+            commands::export::export_jots,
+            commands::import::import_jots,
+            commands::links::refresh_jot_links,
+            commands::stats::get_jot_timeline,
+            commands::stats::get_tag_frequency,
+            commands::stats::get_jot_stats,
+            commands::review::get_review_queue,
+            commands::review::record_review_outcome,
+            commands::tasks::list_jot_tasks,
+            commands::tasks::toggle_jot_task,
+            commands::reminders::list_reminders,
+            commands::reminders::snooze_reminder,
+            commands::reminders::dismiss_reminder,
+            commands::templates::list_templates,
+            commands::templates::save_template,
+            commands::templates::delete_template,
+            commands::templates::create_jot_from_template,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
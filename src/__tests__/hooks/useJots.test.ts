@@ -15,6 +15,9 @@ const mockJot1: Jot = {
   tags: ['work'],
   links: ['Project A'],
   promoted: false,
+  archived: false,
+  external_links: [],
+  properties: {},
   file_path: '/path/to/jot-1.md'
 };
 
@@ -26,6 +29,9 @@ const mockJot2: Jot = {
   tags: ['personal'],
   links: [],
   promoted: false,
+  archived: false,
+  external_links: [],
+  properties: {},
   file_path: '/path/to/jot-2.md'
 };
 
@@ -37,6 +43,9 @@ const mockJot3: Jot = {
   tags: [],
   links: [],
   promoted: false,
+  archived: false,
+  external_links: [],
+  properties: {},
   file_path: '/path/to/jot-3.md'
 };
 
@@ -224,6 +233,78 @@ describe('useJots', () => {
     });
   });
 
+  describe('Updating jots', () => {
+    const conflict = { code: 'conflict', message: 'Jot changed', details: { id: 'jot-1' } };
+    const changedElsewhere: Jot = {
+      ...mockJot1,
+      content: 'First jot\nAdded elsewhere',
+      modified_at: '2025-01-19T12:00:00Z'
+    };
+
+    it('sends the loaded version as the base', async () => {
+      vi.spyOn(jotsApi, 'getJots').mockResolvedValue([mockJot1]);
+      const edited: Jot = { ...mockJot1, content: 'Edited', modified_at: '2025-01-19T13:00:00Z' };
+      const mockUpdateJot = vi.spyOn(jotsApi, 'updateJot').mockResolvedValue(edited);
+
+      const { result } = renderHook(() => useJots());
+      await waitFor(() => {
+        expect(result.current.loading).toBe(false);
+      });
+
+      const outcome = await result.current.updateJot('jot-1', 'Edited');
+
+      expect(mockUpdateJot).toHaveBeenCalledWith(mockJot1, 'Edited');
+      expect(outcome).toEqual({ status: 'saved', jot: edited });
+      await waitFor(() => {
+        expect(result.current.jots[0].content).toBe('Edited');
+      });
+    });
+
+    it('merges a conflicting edit and saves it against the current version', async () => {
+      vi.spyOn(jotsApi, 'getJots').mockResolvedValue([mockJot1]);
+      const merged = 'Edited jot\nAdded elsewhere';
+      const saved: Jot = { ...changedElsewhere, content: merged, modified_at: '2025-01-19T13:00:00Z' };
+      const mockUpdateJot = vi.spyOn(jotsApi, 'updateJot')
+        .mockRejectedValueOnce(conflict)
+        .mockResolvedValueOnce(saved);
+      vi.spyOn(jotsApi, 'mergeJotUpdate').mockResolvedValue({ content: merged, has_conflicts: false });
+      vi.spyOn(jotsApi, 'getJot').mockResolvedValue(changedElsewhere);
+
+      const { result } = renderHook(() => useJots());
+      await waitFor(() => {
+        expect(result.current.loading).toBe(false);
+      });
+
+      const outcome = await result.current.updateJot('jot-1', 'Edited jot');
+
+      expect(jotsApi.mergeJotUpdate).toHaveBeenCalledWith(mockJot1, 'Edited jot');
+      expect(mockUpdateJot).toHaveBeenLastCalledWith(changedElsewhere, merged);
+      expect(outcome).toEqual({ status: 'saved', jot: saved });
+      expect(result.current.error).toBeNull();
+    });
+
+    it('returns the conflict markers when both edits touch the same lines', async () => {
+      vi.spyOn(jotsApi, 'getJots').mockResolvedValue([mockJot1]);
+      const mockUpdateJot = vi.spyOn(jotsApi, 'updateJot').mockRejectedValue(conflict);
+      const merged = '<<<<<<< yours\nMine\n=======\nTheirs\n>>>>>>> theirs';
+      vi.spyOn(jotsApi, 'mergeJotUpdate').mockResolvedValue({ content: merged, has_conflicts: true });
+      vi.spyOn(jotsApi, 'getJot').mockResolvedValue(changedElsewhere);
+
+      const { result } = renderHook(() => useJots());
+      await waitFor(() => {
+        expect(result.current.loading).toBe(false);
+      });
+
+      const outcome = await result.current.updateJot('jot-1', 'Mine');
+
+      expect(mockUpdateJot).toHaveBeenCalledTimes(1);
+      expect(outcome).toEqual({ status: 'conflict', current: changedElsewhere, merged });
+      await waitFor(() => {
+        expect(result.current.jots[0]).toEqual(changedElsewhere);
+      });
+    });
+  });
+
   describe('Deleting jots with optimistic updates', () => {
     it('removes jot immediately (optimistic update)', async () => {
       vi.spyOn(jotsApi, 'getJots').mockResolvedValue([mockJot1, mockJot2]);
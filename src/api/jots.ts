@@ -1,5 +1,13 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { Jot, CreateJotInput, UpdateJotInput } from '../types/jot';
+import type {
+  Jot,
+  CreateJotInput,
+  UpdateJotInput,
+  JotFilter,
+  JotListQuery,
+  JotPage,
+  MergeResult,
+} from '../types/jot';
 
 /**
  * Tauri API wrappers for jot operations
@@ -24,21 +32,31 @@ export async function createJot(content: string): Promise<Jot> {
 }
 
 /**
- * Get all jots with pagination
+ * Get one page of jots
+ *
+ * @param query - Sort, filter, page size (default: 50, max: 500) and the cursor of the previous page
+ * @returns The page, the cursor for the next page (null on the last page) and the total count
+ *
+ * @example
+ * const page = await listJots({ sort: 'modified', direction: 'desc', limit: 50 });
+ * const next = await listJots({ sort: 'modified', direction: 'desc', limit: 50, cursor: page.next_cursor });
+ */
+export async function listJots(query: JotListQuery = {}): Promise<JotPage> {
+  return await invoke<JotPage>('get_jots', { query });
+}
+
+/**
+ * Get the first jots, oldest first
  *
  * @param limit - Maximum number of jots to return (default: 50, max: 500)
- * @param offset - Number of jots to skip (default: 0)
  * @returns Array of jots sorted by created_at ascending (oldest first)
  *
  * @example
- * const jots = await getJots(50, 0); // First 50 jots
- * const moreJots = await getJots(50, 50); // Next 50 jots
+ * const jots = await getJots(50); // First 50 jots
  */
-export async function getJots(limit?: number, offset?: number): Promise<Jot[]> {
-  return await invoke<Jot[]>('get_jots', {
-    limit: limit ?? 50,
-    offset: offset ?? 0
-  });
+export async function getJots(limit?: number): Promise<Jot[]> {
+  const page = await listJots({ limit: limit ?? 50 });
+  return page.jots;
 }
 
 /**
@@ -55,20 +73,47 @@ export async function getJot(id: string): Promise<Jot> {
   return await invoke<Jot>('get_jot', { id });
 }
 
+/**
+ * Build the update input for an edit of a loaded jot
+ * The loaded version is the base the backend checks for concurrent changes
+ */
+function editInput(jot: Jot, content: string): UpdateJotInput {
+  return {
+    id: jot.id,
+    content: content.trim(),
+    base_modified_at: jot.modified_at,
+    base_content: jot.content,
+  };
+}
+
 /**
  * Update a jot's content
  *
- * @param id - The jot ID
+ * @param jot - The jot as loaded (the version being edited)
  * @param content - The new content
  * @returns The updated jot with new modified_at timestamp
- * @throws Error if jot not found or content is invalid
+ * @throws JotError with code "conflict" if the jot changed since it was loaded
+ *   (see mergeJotUpdate), or if the jot is not found or content is invalid
+ *
+ * @example
+ * const updated = await updateJot(jot, "Updated content");
+ */
+export async function updateJot(jot: Jot, content: string): Promise<Jot> {
+  return await invoke<Jot>('update_jot', { input: editInput(jot, content) });
+}
+
+/**
+ * Merge an edit of a loaded jot with the jot's current content
+ *
+ * @param jot - The jot as loaded (the common ancestor)
+ * @param content - The edited content
+ * @returns The merged content; has_conflicts if it holds conflict markers to resolve
  *
  * @example
- * const updated = await updateJot("jot-2026-01-19-143256-a1b2", "Updated content");
+ * const merge = await mergeJotUpdate(jot, "Edited content");
  */
-export async function updateJot(id: string, content: string): Promise<Jot> {
-  const input: UpdateJotInput = { id, content: content.trim() };
-  return await invoke<Jot>('update_jot', { input });
+export async function mergeJotUpdate(jot: Jot, content: string): Promise<MergeResult> {
+  return await invoke<MergeResult>('merge_jot_update', { input: editInput(jot, content) });
 }
 
 /**
@@ -93,14 +138,16 @@ export async function deleteJot(id: string): Promise<void> {
  *
  * @param query - The search query
  * @param limit - Maximum number of results (default: 50)
+ * @param filter - Optional filter narrowing the results (tags, properties, ...)
  * @returns Array of matching jots
  *
  * @example
- * const results = await searchJots("meeting", 20);
+ * const results = await searchJots("meeting", 20, { properties: { project: "Apollo" } });
  */
-export async function searchJots(query: string, limit?: number): Promise<Jot[]> {
+export async function searchJots(query: string, limit?: number, filter?: JotFilter): Promise<Jot[]> {
   return await invoke<Jot[]>('search_jots', {
     query,
+    filter,
     limit: limit ?? 50
   });
 }
@@ -1,12 +1,31 @@
 import { useState, useEffect, useCallback, useRef } from 'react';
-import { createJot as apiCreateJot, getJots as apiGetJots, deleteJot as apiDeleteJot } from '../api/jots';
+import {
+  createJot as apiCreateJot,
+  getJots as apiGetJots,
+  getJot as apiGetJot,
+  updateJot as apiUpdateJot,
+  mergeJotUpdate as apiMergeJotUpdate,
+  deleteJot as apiDeleteJot
+} from '../api/jots';
 import type { Jot } from '../types/jot';
+import { isJotError } from '../utils/jotError';
+
+/**
+ * Outcome of saving an edit
+ * - saved: the edit (merged with any concurrent change) was written
+ * - conflict: the jot changed elsewhere in the same lines; `merged` holds conflict
+ *   markers for the user to resolve, then save again (the jot in state is now `current`)
+ */
+export type UpdateJotOutcome =
+  | { status: 'saved'; jot: Jot }
+  | { status: 'conflict'; current: Jot; merged: string };
 
 interface UseJotsReturn {
   jots: Jot[];
   loading: boolean;
   error: string | null;
   createJot: (content: string) => Promise<void>;
+  updateJot: (id: string, content: string) => Promise<UpdateJotOutcome>;
   deleteJot: (id: string) => Promise<void>;
   refresh: () => Promise<void>;
 }
@@ -18,6 +37,7 @@ interface UseJotsReturn {
  * - Loads jots on mount
  * - Optimistic updates for create (instant UI feedback)
  * - Optimistic updates for delete (instant UI feedback)
+ * - Edits carry the loaded version, so changes made elsewhere are merged, not overwritten
  * - Automatic rollback on error
  * - Sorts jots by created_at ascending (oldest first)
  *
@@ -40,6 +60,10 @@ export function useJots(): UseJotsReturn {
   // Ref to store snapshot for rollback
   const rollbackSnapshotRef = useRef<Jot[]>([]);
 
+  // Latest jots, so updateJot can find the loaded version without changing identity
+  const jotsRef = useRef<Jot[]>([]);
+  jotsRef.current = jots;
+
   const loadJots = useCallback(async () => {
     try {
       setLoading(true);
@@ -76,6 +100,9 @@ export function useJots(): UseJotsReturn {
       tags: [],
       links: [],
       promoted: false,
+      archived: false,
+      external_links: [],
+      properties: {},
       file_path: ''
     };
 
@@ -104,6 +131,49 @@ export function useJots(): UseJotsReturn {
     }
   }, []);
 
+  const updateJot = useCallback(async (id: string, content: string): Promise<UpdateJotOutcome> => {
+    const replace = (jot: Jot) =>
+      setJots(prev => prev.map(existing => existing.id === jot.id ? jot : existing));
+
+    setError(null);
+
+    try {
+      const loaded = jotsRef.current.find(jot => jot.id === id) ?? await apiGetJot(id);
+
+      try {
+        const updated = await apiUpdateJot(loaded, content);
+        replace(updated);
+        return { status: 'saved', jot: updated };
+      } catch (err) {
+        if (!isJotError(err, 'conflict')) {
+          throw err;
+        }
+      }
+
+      // Changed elsewhere since it was loaded: merge the edit into the current version
+      const merge = await apiMergeJotUpdate(loaded, content);
+      const current = await apiGetJot(id);
+      replace(current);
+
+      if (merge.has_conflicts) {
+        return { status: 'conflict', current, merged: merge.content };
+      }
+
+      const updated = await apiUpdateJot(current, merge.content);
+      replace(updated);
+      return { status: 'saved', jot: updated };
+    } catch (err) {
+      const errorMessage = err instanceof Error
+        ? err.message
+        : isJotError(err, 'conflict') ? 'Jot changed while saving, try again' : 'Failed to update jot';
+      setError(errorMessage);
+      console.error('Failed to update jot:', err);
+
+      // Re-throw for component error handling
+      throw err;
+    }
+  }, []);
+
   const deleteJot = useCallback(async (id: string) => {
     // Store snapshot and perform optimistic update
     setJots(prev => {
@@ -142,6 +212,7 @@ export function useJots(): UseJotsReturn {
     loading,
     error,
     createJot,
+    updateJot,
     deleteJot,
     refresh
   };
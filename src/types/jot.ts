@@ -2,7 +2,7 @@
  * Jot type definition matching the Rust backend
  */
 export interface Jot {
-  /** Unique identifier: "jot-YYYY-MM-DD-HHMMSS-XXXX" */
+  /** Unique identifier: "jot-" + lowercase ULID (older jots: "jot-YYYY-MM-DD-HHMMSS-XXXX") */
   id: string;
 
   /** The jot text (without frontmatter) */
@@ -23,10 +23,37 @@ export interface Jot {
   /** Whether jot has been promoted to a full note */
   promoted: boolean;
 
+  /** Whether jot was processed and put away without becoming a note */
+  archived: boolean;
+
+  /** Metadata fetched for URLs in the content */
+  external_links: ExternalLink[];
+
+  /** Custom properties: extra frontmatter keys and inline `key:: value` fields */
+  properties: Record<string, unknown>;
+
   /** Relative path from vault root */
   file_path: string;
 }
 
+/**
+ * Metadata of a web page linked from a jot
+ */
+export interface ExternalLink {
+  /** URL as it appears in the jot */
+  url: string;
+  title?: string;
+  description?: string;
+  canonical_url?: string;
+  site_name?: string;
+
+  /** Start of the page's main text */
+  excerpt?: string;
+
+  /** ISO 8601 */
+  fetched_at: string;
+}
+
 /**
  * Input for creating a new jot
  */
@@ -40,24 +67,228 @@ export interface CreateJotInput {
 export interface UpdateJotInput {
   id: string;
   content: string;
+
+  /** modified_at of the version being edited; the update is rejected if it changed */
+  base_modified_at?: string;
+
+  /** Content of the version being edited; also the common ancestor for merges */
+  base_content?: string;
+}
+
+/**
+ * Three-way merge of an edit with a jot's current content
+ */
+export interface MergeResult {
+  /** Merged text, with conflict markers around unresolved regions */
+  content: string;
+
+  /** Whether the content contains conflict markers for the user to resolve */
+  has_conflicts: boolean;
+}
+
+/**
+ * Field jot listings are sorted by
+ */
+export type JotSortField = "created" | "modified";
+
+/**
+ * Sort direction for jot listings
+ */
+export type SortDirection = "asc" | "desc";
+
+/**
+ * Which jots a listing or export includes (all fields optional)
+ */
+export interface JotFilter {
+  /** Only promoted (true) or unpromoted (false) jots */
+  promoted?: boolean;
+
+  /** Default: "exclude" (archived jots are hidden) */
+  archived?: "exclude" | "include" | "only";
+
+  /** Only jots created at or after this time (ISO 8601) */
+  created_from?: string;
+
+  /** Only jots created before this time (ISO 8601) */
+  created_to?: string;
+
+  /** Only jots having every one of these tags */
+  tags?: string[];
+
+  /** Only jots having every one of these property values (case-insensitive) */
+  properties?: Record<string, string>;
 }
 
 /**
- * Error types for jot operations
+ * Query for one page of jots (all fields optional)
  */
-export type JotErrorType =
+export interface JotListQuery extends JotFilter {
+  /** Default: "created" */
+  sort?: JotSortField;
+
+  /** Default: "asc" */
+  direction?: SortDirection;
+
+  /** next_cursor of the previous page */
+  cursor?: string | null;
+
+  /** Default: 50, max: 500 */
+  limit?: number;
+}
+
+/**
+ * One page of a jot listing
+ */
+export interface JotPage {
+  jots: Jot[];
+
+  /** Cursor for the next page; null on the last page */
+  next_cursor: string | null;
+
+  /** Number of jots matching the filter, across all pages */
+  total_count: number;
+}
+
+/**
+ * Priority of a checkbox task (Obsidian Tasks markers or `priority: high`)
+ */
+export type TaskPriority = "lowest" | "low" | "normal" | "medium" | "high" | "highest";
+
+/**
+ * Checkbox task (`- [ ] call Bob`) inside a jot
+ */
+export interface JotTask {
+  jot_id: string;
+
+  /** Line of the jot content the task is on (0-based) */
+  line: number;
+
+  /** Task text without the checkbox, due date and priority markers */
+  text: string;
+
+  done: boolean;
+
+  /** Due date (YYYY-MM-DD) */
+  due: string | null;
+
+  priority: TaskPriority;
+}
+
+/**
+ * Query for tasks across jots (all fields optional)
+ */
+export interface TaskQuery {
+  /** Default: "open" */
+  status?: "open" | "overdue" | "done" | "all";
+
+  /** The user's local date (YYYY-MM-DD) overdue is judged against; default: today */
+  today?: string;
+
+  /** Default: "exclude" (tasks of archived jots are hidden) */
+  archived?: "exclude" | "include" | "only";
+}
+
+/**
+ * State of a reminder: pending until remind_at, fired once notified, dismissed when done
+ */
+export type ReminderStatus = "pending" | "fired" | "dismissed";
+
+/**
+ * Reminder extracted from a jot line such as "remind me to review PR tomorrow at 3pm"
+ * Emitted with the "reminder-due" event when it comes due
+ */
+export interface Reminder {
+  id: number;
+
+  jot_id: string;
+
+  /** What to be reminded of ("review PR") */
+  text: string;
+
+  /** When the jot said (ISO 8601) */
+  due_at: string;
+
+  /** When the reminder fires: due_at, or later once snoozed (ISO 8601) */
+  remind_at: string;
+
+  status: ReminderStatus;
+}
+
+/**
+ * Jot template stored as Markdown in the vault's .scribel/templates folder
+ */
+export interface JotTemplate {
+  /** File name without .md ("Meeting") */
+  name: string;
+
+  description: string | null;
+
+  /** Tags added to every jot created from the template (without '#') */
+  tags: string[];
+
+  /** Body with {{date}}, {{time}}, {{clipboard}}, {{cursor}} and {{prompt:Label}} placeholders */
+  content: string;
+
+  /** Labels of the {{prompt:Label}} placeholders, in order of appearance */
+  prompts: string[];
+}
+
+/**
+ * Values for a template's placeholders (all fields optional)
+ */
+export interface TemplateValues {
+  /** Answers by prompt label; unanswered prompts are left empty */
+  prompts?: Record<string, string>;
+
+  /** Clipboard text for {{clipboard}} */
+  clipboard?: string;
+}
+
+/**
+ * Jot created from a template
+ */
+export interface TemplateJot {
+  jot: Jot;
+
+  /** Where {{cursor}} was, as an offset into the jot content */
+  cursor: number | null;
+}
+
+/**
+ * Error codes returned by backend commands
+ */
+export type JotErrorCode =
   | "not_found"
+  | "already_exists"
+  | "conflict"
   | "file_error"
   | "parse_error"
   | "db_error"
+  | "db_unavailable"
   | "vault_not_configured"
   | "validation_error"
-  | "unknown";
+  | "network_error"
+  | "internal";
+
+/**
+ * Content rule a jot failed (configured in the app's validation settings)
+ */
+export type ValidationRule = "not_empty" | "max_length" | "required_tag" | "forbidden_word";
 
 /**
- * Jot error structure
+ * Error structure rejected by backend commands
  */
 export interface JotError {
-  type: JotErrorType;
+  code: JotErrorCode;
   message: string;
+
+  /**
+   * Structured context: the jot `id`, the invalid `field` (with the failed content `rule`),
+   * or the unparseable `file`
+   */
+  details?:
+    | { id: string }
+    | { field: string }
+    | { field: string; rule: ValidationRule }
+    | { file: string };
 }
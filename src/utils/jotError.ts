@@ -0,0 +1,20 @@
+import type { JotError, JotErrorCode } from '../types/jot';
+
+/**
+ * Check whether a value rejected by a backend command is a JotError with the given code
+ *
+ * @param err - The caught value
+ * @param code - The expected error code
+ * @returns true if err is a JotError with that code
+ *
+ * @example
+ * isJotError(err, 'conflict') // true when the jot changed since it was loaded
+ */
+export function isJotError(err: unknown, code: JotErrorCode): err is JotError {
+  return (
+    typeof err === 'object' &&
+    err !== null &&
+    'code' in err &&
+    (err as { code: unknown }).code === code
+  );
+}